@@ -0,0 +1,327 @@
+use std::io::{self, Read, Write, Seek, BufRead};
+
+/// A writer which inserts a prefix at the start of every line written through it.
+///
+/// This is created by the `prefix()` method on `Burrito`. The prefix is written lazily, when the
+/// first byte of a line is written, so output which ends with a newline is not followed by a
+/// dangling prefix.
+pub struct PrefixWriter<T> {
+    inner: T,
+    prefix: String,
+    at_line_start: bool,
+}
+
+impl<T: Write> PrefixWriter<T> {
+
+    /// Wraps `inner`, prefixing every line written to it with `prefix`.
+    pub fn new(inner: T, prefix: String) -> PrefixWriter<T> {
+        PrefixWriter { inner, prefix, at_line_start: true }
+    }
+
+    /// Unwraps the writer, returning the underlying handle.
+    pub fn into_inner(self) -> T { self.inner }
+
+}
+
+impl<T: Write> Write for PrefixWriter<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() { return Ok(0) }
+        if self.at_line_start {
+            self.inner.write_all(self.prefix.as_bytes())?;
+            self.at_line_start = false;
+        }
+        let end = buf.iter().position(|&b| b == b'\n').map_or(buf.len(), |i| i + 1);
+        let n = self.inner.write(&buf[..end])?;
+        if n > 0 && buf[n - 1] == b'\n' { self.at_line_start = true; }
+        Ok(n)
+    }
+    fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
+}
+
+/// A handle which counts the calls made to its `read` and `write` methods.
+///
+/// This is created by the `instrument()` method on `Burrito`. Wrapping a handle in a buffer after
+/// instrumenting it shows how many calls actually reach the handle.
+pub struct Instrumented<T> {
+    inner: T,
+    read_calls: u64,
+    write_calls: u64,
+}
+
+impl<T> Instrumented<T> {
+
+    /// Wraps `inner`, counting calls made to it.
+    pub fn new(inner: T) -> Instrumented<T> {
+        Instrumented { inner, read_calls: 0, write_calls: 0 }
+    }
+
+    /// The number of calls made to `read`.
+    pub fn read_calls(&self) -> u64 { self.read_calls }
+
+    /// The number of calls made to `write`.
+    pub fn write_calls(&self) -> u64 { self.write_calls }
+
+    /// Unwraps the handle, returning the underlying handle.
+    pub fn into_inner(self) -> T { self.inner }
+
+}
+
+impl<T: Read> Read for Instrumented<T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.read_calls += 1;
+        self.inner.read(buf)
+    }
+}
+
+impl<T: Write> Write for Instrumented<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_calls += 1;
+        self.inner.write(buf)
+    }
+    fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
+}
+
+impl<T: Seek> Seek for Instrumented<T> {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> { self.inner.seek(pos) }
+}
+
+/// A writer which flushes the underlying handle when it is dropped.
+///
+/// This is created by the `flush_on_drop()` method on `Burrito`. Because `drop` cannot return an
+/// error, any error flushing the handle when it is dropped is ignored; use the `close()` method
+/// on `Burrito` to flush the handle and check for errors.
+pub struct FlushOnDrop<T: Write> {
+    inner: T,
+}
+
+impl<T: Write> FlushOnDrop<T> {
+
+    /// Wraps `inner`, flushing it when dropped.
+    pub fn new(inner: T) -> FlushOnDrop<T> { FlushOnDrop { inner } }
+
+}
+
+impl<T: Write> Write for FlushOnDrop<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.inner.write(buf) }
+    fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
+}
+
+impl<T: Write> Drop for FlushOnDrop<T> {
+    fn drop(&mut self) {
+        let _ = self.inner.flush();
+    }
+}
+
+/// A reader which copies every byte read through it to a side writer.
+///
+/// This is created by the `tee_read()` method on `Burrito`. The bytes are written to the sink
+/// before they are returned by `read`; if writing them to the sink fails, that error is returned
+/// by `read` instead, and the bytes are lost.
+pub struct TeeReader<T, W> {
+    inner: T,
+    sink: W,
+}
+
+impl<T: Read, W: Write> TeeReader<T, W> {
+
+    /// Wraps `inner`, copying everything read from it to `sink`.
+    pub fn new(inner: T, sink: W) -> TeeReader<T, W> { TeeReader { inner, sink } }
+
+    /// Unwraps the reader, returning the underlying handle and the sink.
+    pub fn into_inner(self) -> (T, W) { (self.inner, self.sink) }
+
+}
+
+impl<T: Read, W: Write> Read for TeeReader<T, W> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.sink.write_all(&buf[..n])?;
+        Ok(n)
+    }
+}
+
+/// The line ending which a `LineEndingWriter` converts newlines to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineEnding {
+    /// Unix line endings: every `\r\n` is written as `\n`.
+    Lf,
+    /// Windows line endings: every `\n` which is not already preceded by `\r` is written as
+    /// `\r\n`.
+    CrLf,
+}
+
+/// A writer which converts the line endings of everything written through it.
+///
+/// This is created by the `convert_line_endings()` method on `Burrito`. A `\r\n` split across two
+/// writes is recognized as a line ending. When converting to `LineEnding::Lf`, a `\r` at the end
+/// of a write can't be written until the next write shows whether it begins with `\n`, so it is
+/// held back, even by `flush`; it is written by the next write or by `into_inner`, and is lost if
+/// the writer is dropped instead.
+pub struct LineEndingWriter<T> {
+    inner: T,
+    ending: LineEnding,
+    // Whether the last byte written was `\r`; when converting to `Lf`, that `\r` is held back.
+    after_cr: bool,
+}
+
+impl<T: Write> LineEndingWriter<T> {
+
+    /// Wraps `inner`, converting line endings written to it to `ending`.
+    pub fn new(inner: T, ending: LineEnding) -> LineEndingWriter<T> {
+        LineEndingWriter { inner, ending, after_cr: false }
+    }
+
+    /// Writes any `\r` being held back, and unwraps the writer, returning the underlying handle.
+    pub fn into_inner(mut self) -> io::Result<T> {
+        if self.ending == LineEnding::Lf && self.after_cr {
+            self.inner.write_all(b"\r")?;
+        }
+        Ok(self.inner)
+    }
+
+    fn write_crlf(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let bare_lf = buf.iter().enumerate().position(|(i, &b)| {
+            b == b'\n' && !if i == 0 { self.after_cr } else { buf[i - 1] == b'\r' }
+        });
+        let n = match bare_lf {
+            Some(0) => {
+                self.inner.write_all(b"\r\n")?;
+                1
+            }
+            Some(end) => self.inner.write(&buf[..end])?,
+            None => self.inner.write(buf)?,
+        };
+        if n > 0 { self.after_cr = buf[n - 1] == b'\r'; }
+        Ok(n)
+    }
+
+    fn write_lf(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.after_cr {
+            self.inner.write_all(if buf[0] == b'\n' { b"\n" } else { b"\r" })?;
+            self.after_cr = false;
+            if buf[0] == b'\n' { return Ok(1) }
+        }
+        match buf.iter().position(|&b| b == b'\r') {
+            Some(0) if buf.len() == 1 => {
+                self.after_cr = true;
+                Ok(1)
+            }
+            Some(0) if buf[1] == b'\n' => {
+                self.inner.write_all(b"\n")?;
+                Ok(2)
+            }
+            Some(0) => {
+                self.inner.write_all(b"\r")?;
+                Ok(1)
+            }
+            Some(end) => self.inner.write(&buf[..end]),
+            None => self.inner.write(buf),
+        }
+    }
+
+}
+
+impl<T: Write> Write for LineEndingWriter<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() { return Ok(0) }
+        match self.ending {
+            LineEnding::Lf => self.write_lf(buf),
+            LineEnding::CrLf => self.write_crlf(buf),
+        }
+    }
+    fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
+}
+
+/// A handle which fails once more than a fixed number of bytes have been read from or written to
+/// it in total.
+///
+/// This is created by the `with_quota()` method on `Burrito`. Reads and writes are shortened so
+/// that they don't go over the quota; once it has been used up, any further write fails with
+/// the message "quota exceeded", as does any further read, unless the handle is at EOF.
+///
+/// Finding out whether the handle is at EOF takes a `read` of one byte. If there is a byte, it is
+/// held by the `Quota` rather than lost, and can be retrieved with `overflow()` before the handle
+/// is unwrapped. A `fill_buf` can look at the handle without reading from it, so it never holds a
+/// byte.
+pub struct Quota<T> {
+    inner: T,
+    limit: u64,
+    used: u64,
+    overflow: Option<u8>,
+}
+
+impl<T> Quota<T> {
+
+    /// Wraps `inner`, allowing `limit` bytes to be read from or written to it in total.
+    pub fn new(inner: T, limit: u64) -> Quota<T> {
+        Quota { inner, limit, used: 0, overflow: None }
+    }
+
+    /// The number of bytes which have been read or written.
+    pub fn used(&self) -> u64 { self.used }
+
+    /// The number of bytes which can still be read or written.
+    pub fn remaining(&self) -> u64 { self.limit.saturating_sub(self.used) }
+
+    /// The byte read from the handle past the quota, if a read has failed because there was one.
+    pub fn overflow(&self) -> Option<u8> { self.overflow }
+
+    /// Unwraps the handle, returning the underlying handle. A byte held from going over the quota
+    /// is not returned to it; see `overflow()`.
+    pub fn into_inner(self) -> T { self.inner }
+
+    fn allowed(&self, len: usize) -> usize {
+        if (len as u64) < self.remaining() { len } else { self.remaining() as usize }
+    }
+
+}
+
+fn quota_exceeded() -> io::Error { io::Error::new(io::ErrorKind::Other, "quota exceeded") }
+
+impl<T: Read> Read for Quota<T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() { return Ok(0) }
+        if self.overflow.is_some() { return Err(quota_exceeded()) }
+        if self.remaining() == 0 {
+            // Reading at EOF is allowed; a byte which would go over the quota is held, not lost.
+            let mut byte = [0];
+            return match self.inner.read(&mut byte)? {
+                0 => Ok(0),
+                _ => {
+                    self.overflow = Some(byte[0]);
+                    Err(quota_exceeded())
+                }
+            }
+        }
+        let len = self.allowed(buf.len());
+        let n = self.inner.read(&mut buf[..len])?;
+        self.used += n as u64;
+        Ok(n)
+    }
+}
+
+impl<T: BufRead> BufRead for Quota<T> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.overflow.is_some() { return Err(quota_exceeded()) }
+        let remaining = self.remaining();
+        let buf = self.inner.fill_buf()?;
+        if remaining == 0 && !buf.is_empty() { return Err(quota_exceeded()) }
+        if (buf.len() as u64) < remaining { Ok(buf) } else { Ok(&buf[..remaining as usize]) }
+    }
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.used += amt as u64;
+    }
+}
+
+impl<T: Write> Write for Quota<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() { return Ok(0) }
+        if self.remaining() == 0 { return Err(quota_exceeded()) }
+        let len = self.allowed(buf.len());
+        let n = self.inner.write(&buf[..len])?;
+        self.used += n as u64;
+        Ok(n)
+    }
+    fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
+}
@@ -1,8 +1,8 @@
 use std::convert::AsRef;
 use std::fs;
 use std::net::{self, ToSocketAddrs};
-use std::path::Path;
-use std::io;
+use std::path::{Path, PathBuf};
+use std::io::{self, Read, Write, Seek};
 
 pub trait FromPath {
     fn from_path<P: AsRef<Path>>(P) -> io::Result<Self>;
@@ -18,6 +18,83 @@ impl FromPath for fs::File {
     }
 }
 
+/// A newly created file. Its implementation of `FromPath` creates the file, failing with an
+/// error of the kind `io::ErrorKind::AlreadyExists` if it already exists, which makes it suitable
+/// for lockfiles.
+pub struct NewFile(pub fs::File);
+
+impl FromPath for NewFile {
+    fn from_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        fs::OpenOptions::new().read(true).write(true).create_new(true).open(path).map(NewFile)
+    }
+}
+
+impl Read for NewFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> { self.0.read(buf) }
+}
+
+impl Write for NewFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.0.write(buf) }
+    fn flush(&mut self) -> io::Result<()> { self.0.flush() }
+}
+
+impl Seek for NewFile {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> { self.0.seek(pos) }
+}
+
+/// A file which remembers its path. Every error reading, writing or seeking it includes the path
+/// in its message, so that a failure deep in a chain of calls says which file it came from. Its
+/// implementation of `FromPath` opens the file as `File`'s does.
+pub struct NamedFile {
+    path: PathBuf,
+    file: fs::File,
+}
+
+impl NamedFile {
+
+    /// The path the file was opened with.
+    pub fn path(&self) -> &Path { &self.path }
+
+    /// Unwraps the underlying `File`.
+    pub fn into_inner(self) -> fs::File { self.file }
+
+    fn annotate(&self, err: io::Error) -> io::Error {
+        io::Error::new(err.kind(), format!("{}: {}", self.path.display(), err))
+    }
+
+}
+
+impl FromPath for NamedFile {
+    fn from_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        match fs::File::from_path(&path) {
+            Ok(file) => Ok(NamedFile { path, file }),
+            Err(err) => Err(io::Error::new(err.kind(), format!("{}: {}", path.display(), err))),
+        }
+    }
+}
+
+impl Read for NamedFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.file.read(buf).map_err(|err| self.annotate(err))
+    }
+}
+
+impl Write for NamedFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf).map_err(|err| self.annotate(err))
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush().map_err(|err| self.annotate(err))
+    }
+}
+
+impl Seek for NamedFile {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        self.file.seek(pos).map_err(|err| self.annotate(err))
+    }
+}
+
 impl FromAddr for net::TcpStream {
     fn from_addr<A: ToSocketAddrs>(addr: A) -> io::Result<net::TcpStream> {
         net::TcpStream::connect(addr)
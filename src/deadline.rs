@@ -0,0 +1,64 @@
+use std::io::{self, Read, Write};
+use std::net;
+use std::time::{Duration, Instant};
+
+/// A `TcpStream` which fails every read and write that doesn't finish before a deadline.
+///
+/// This is created by the `set_deadline()` method on `Burrito`. Before each read or write, the
+/// stream's timeout is set to the time remaining until the deadline, so the deadline bounds the
+/// whole sequence of calls rather than each call separately. Once the deadline has passed, every
+/// read and write fails with an error of the kind `io::ErrorKind::TimedOut`.
+pub struct DeadlineStream {
+    stream: net::TcpStream,
+    deadline: Instant,
+}
+
+impl DeadlineStream {
+
+    /// Wraps `stream`, failing reads and writes which don't finish before `deadline`.
+    pub fn new(stream: net::TcpStream, deadline: Instant) -> DeadlineStream {
+        DeadlineStream { stream, deadline }
+    }
+
+    /// The deadline for reads and writes on this stream.
+    pub fn deadline(&self) -> Instant { self.deadline }
+
+    /// A reference to the underlying `TcpStream`.
+    pub fn get_ref(&self) -> &net::TcpStream { &self.stream }
+
+    /// Unwraps the stream, returning the underlying `TcpStream`. Its read and write timeouts are
+    /// left as they were last set.
+    pub fn into_inner(self) -> net::TcpStream { self.stream }
+
+    fn remaining(&self) -> io::Result<Duration> {
+        match self.deadline.checked_duration_since(Instant::now()) {
+            Some(remaining) if remaining > Duration::from_secs(0) => Ok(remaining),
+            _ => Err(io::Error::new(io::ErrorKind::TimedOut, "deadline has passed")),
+        }
+    }
+
+    fn timed_out(&self, err: io::Error) -> io::Error {
+        let is_timeout = matches!(err.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut);
+        if is_timeout && Instant::now() >= self.deadline {
+            io::Error::new(io::ErrorKind::TimedOut, "deadline has passed")
+        } else {
+            err
+        }
+    }
+
+}
+
+impl Read for DeadlineStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.stream.set_read_timeout(Some(self.remaining()?))?;
+        self.stream.read(buf).map_err(|err| self.timed_out(err))
+    }
+}
+
+impl Write for DeadlineStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.stream.set_write_timeout(Some(self.remaining()?))?;
+        self.stream.write(buf).map_err(|err| self.timed_out(err))
+    }
+    fn flush(&mut self) -> io::Result<()> { self.stream.flush() }
+}
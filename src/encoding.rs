@@ -0,0 +1,64 @@
+//! Base64 adapters, available with the `base64` feature.
+
+use std::io::{self, Read, Write};
+
+use base64::engine::general_purpose::{GeneralPurpose, STANDARD};
+use base64::read::DecoderReader;
+use base64::write::EncoderWriter;
+
+/// A writer which base64 encodes (with the standard alphabet and padding) every byte written
+/// through it.
+///
+/// This is created by the `base64_encode()` method on `Burrito`. Base64 encodes bytes in groups
+/// of three, so up to two bytes of each write may be held back until the next write completes
+/// their group. The writer must be finished with `base64_finish()` (or `finish()`) to encode the
+/// final partial group and its padding; dropping it will attempt to do so, but cannot report
+/// errors.
+pub struct Base64Writer<T: Write> {
+    inner: EncoderWriter<'static, GeneralPurpose, T>,
+}
+
+impl<T: Write> Base64Writer<T> {
+
+    /// Wraps `inner`, encoding everything written to it.
+    pub fn new(inner: T) -> Base64Writer<T> {
+        Base64Writer { inner: EncoderWriter::new(inner, &STANDARD) }
+    }
+
+    /// Encodes the final partial group and padding, and returns the underlying handle.
+    pub fn finish(mut self) -> io::Result<T> { self.inner.finish() }
+
+}
+
+impl<T: Write> Write for Base64Writer<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.inner.write(buf) }
+    fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
+}
+
+/// A reader which base64 decodes (with the standard alphabet and padding) the bytes read through
+/// it.
+///
+/// This is created by the `base64_decode()` method on `Burrito`. Input is decoded in groups of
+/// four characters, buffering internally as needed, so reads may return fewer bytes than are
+/// available. Whitespace, including newlines, is not accepted in the input; invalid input
+/// results in an error of the kind `io::ErrorKind::InvalidData`.
+pub struct Base64Reader<T: Read> {
+    inner: DecoderReader<'static, GeneralPurpose, T>,
+}
+
+impl<T: Read> Base64Reader<T> {
+
+    /// Wraps `inner`, decoding everything read from it.
+    pub fn new(inner: T) -> Base64Reader<T> {
+        Base64Reader { inner: DecoderReader::new(inner, &STANDARD) }
+    }
+
+    /// Unwraps the reader, returning the underlying handle. Any input which has been read from
+    /// the handle but not yet returned as decoded bytes is lost.
+    pub fn into_inner(self) -> T { self.inner.into_inner() }
+
+}
+
+impl<T: Read> Read for Base64Reader<T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> { self.inner.read(buf) }
+}
@@ -0,0 +1,36 @@
+use std::io::{self, Read, Write};
+use std::net;
+
+/// The reading half of a `TcpStream`, created by the `split_halves()` method on `Burrito`.
+///
+/// Both halves share the same underlying socket, so shutting down either half affects the other.
+pub struct ReadHalf(net::TcpStream);
+
+/// The writing half of a `TcpStream`, created by the `split_halves()` method on `Burrito`.
+///
+/// Both halves share the same underlying socket, so shutting down either half affects the other.
+pub struct WriteHalf(net::TcpStream);
+
+pub fn split(stream: net::TcpStream) -> io::Result<(ReadHalf, WriteHalf)> {
+    let clone = stream.try_clone()?;
+    Ok((ReadHalf(stream), WriteHalf(clone)))
+}
+
+impl ReadHalf {
+    /// A reference to the underlying `TcpStream`.
+    pub fn get_ref(&self) -> &net::TcpStream { &self.0 }
+}
+
+impl WriteHalf {
+    /// A reference to the underlying `TcpStream`.
+    pub fn get_ref(&self) -> &net::TcpStream { &self.0 }
+}
+
+impl Read for ReadHalf {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> { self.0.read(buf) }
+}
+
+impl Write for WriteHalf {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.0.write(buf) }
+    fn flush(&mut self) -> io::Result<()> { self.0.flush() }
+}
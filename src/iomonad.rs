@@ -1,14 +1,126 @@
+use std::borrow::Cow;
+use std::cmp;
+use std::collections::HashSet;
+use std::error;
 use std::fmt;
+use std::fs;
 use std::io::{self, Read, Write, Seek, BufRead};
+use std::net;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use realworld::RealWorld;
+use adapters::{FlushOnDrop, Instrumented, LineEnding, LineEndingWriter, PrefixWriter, Quota,
+               TeeReader};
+#[cfg(feature = "json")]
+use serde;
+#[cfg(feature = "json")]
+use serde_json;
+use deadline::DeadlineStream;
+#[cfg(feature = "base64")]
+use encoding::{Base64Reader, Base64Writer};
+use halves::{self, ReadHalf, WriteHalf};
+use iter::{self, Chunks, Words};
+use realworld::{BufStdin, LockedOut, LockedWorld, RealWorld};
+use reconnect::{self, ReconnectingStream};
+use sys;
 use self::Io::*;
 
+/// How long `write_all_retry` sleeps when a write would block.
+const RETRY_SLEEP_MS: u64 = 10;
+
+/// The size of the chunks read by `read_to_end_progress` and `count_byte`.
+const CHUNK_SIZE: usize = 8 * 1024;
+
 pub enum Io<A, T> {
     Good(A, T),
     Bad(io::Error),
 }
 
+impl<A, T> Io<A, T> {
+
+    pub fn map_handle<U, F: FnOnce(T) -> U>(self, f: F) -> Io<A, U> {
+        match self {
+            Good(data, io) => Good(data, f(io)),
+            Bad(err) => Bad(err),
+        }
+    }
+
+    pub fn try_map_handle<U, F: FnOnce(T) -> io::Result<U>>(self, f: F) -> Io<A, U> {
+        match self {
+            Good(data, io) => {
+                match f(io) {
+                    Ok(io) => Good(data, io),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err),
+        }
+    }
+
+    pub fn instrument(self) -> Io<A, Instrumented<T>> { self.map_handle(Instrumented::new) }
+
+    pub fn with_quota(self, limit: u64) -> Io<A, Quota<T>> {
+        self.map_handle(|io| Quota::new(io, limit))
+    }
+
+    pub fn fuse(self) -> Io<A, T> {
+        match self {
+            Bad(ref err) if is_fused(err) => self,
+            Bad(err) => Bad(io::Error::new(err.kind(), Fused(err))),
+            Good(..) => self,
+        }
+    }
+
+    pub fn is_fused(&self) -> bool {
+        match *self {
+            Good(..) => false,
+            Bad(ref err) => is_fused(err),
+        }
+    }
+
+}
+
+/// An error which has been fused, so that it can't be recovered from. It has the same kind and
+/// message as the original error.
+#[derive(Debug)]
+struct Fused(io::Error);
+
+impl fmt::Display for Fused {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { self.0.fmt(f) }
+}
+
+impl error::Error for Fused {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> { self.0.source() }
+}
+
+fn is_fused(err: &io::Error) -> bool {
+    err.get_ref().is_some_and(|err| err.is::<Fused>())
+}
+
+/// Copies an error's kind and message, and whether it is fused.
+pub fn copy_error(err: &io::Error) -> io::Error {
+    let copy = io::Error::new(err.kind(), err.to_string());
+    if is_fused(err) { io::Error::new(err.kind(), Fused(copy)) } else { copy }
+}
+
+impl<A, T> Io<A, Instrumented<T>> {
+
+    pub fn read_calls(&self) -> u64 {
+        match *self {
+            Good(_, ref io) => io.read_calls(),
+            Bad(..) => 0,
+        }
+    }
+
+    pub fn write_calls(&self) -> u64 {
+        match *self {
+            Good(_, ref io) => io.write_calls(),
+            Bad(..) => 0,
+        }
+    }
+
+}
+
 impl<A, T> Io<A, T> where T: Read {
 
     pub fn read(self, n: usize) -> Io<Vec<u8>, T> {
@@ -27,6 +139,86 @@ impl<A, T> Io<A, T> where T: Read {
         }
     }
 
+    pub fn tee_read<W: Write>(self, sink: W) -> Io<A, TeeReader<T, W>> {
+        self.map_handle(|r| TeeReader::new(r, sink))
+    }
+
+    pub fn read_to_string_lossy(self) -> Io<String, T> {
+        match self.read_to_end() {
+            Good(buf, r) => {
+                let string = match String::from_utf8(buf) {
+                    Ok(string) => string,
+                    Err(err) => String::from_utf8_lossy(err.as_bytes()).into_owned(),
+                };
+                Good(string, r)
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    #[cfg(feature = "base64")]
+    pub fn base64_decode(self) -> Io<A, Base64Reader<T>> {
+        self.map_handle(Base64Reader::new)
+    }
+
+    pub fn hexdump<W: Write>(self, n: usize, out: &mut W) -> Io<Vec<u8>, T> {
+        match self.read(n) {
+            Good(buf, r) => {
+                match write_hexdump(out, &buf) {
+                    Ok(..) => Good(buf, r),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn read_exact_into(self, mut buf: Vec<u8>) -> Io<Vec<u8>, T> {
+        match self {
+            Good(_, mut r) => {
+                match r.read_exact(&mut buf) {
+                    Ok(..) => Good(buf, r),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    #[cfg(feature = "json")]
+    pub fn read_json<V>(self) -> Io<V, T> where V: serde::de::DeserializeOwned {
+        match self {
+            Good(_, mut r) => {
+                let value = serde_json::Deserializer::from_reader(&mut r).into_iter().next();
+                match value {
+                    Some(Ok(value)) => Good(value, r),
+                    Some(Err(err)) => Bad(json_error(err)),
+                    None => Bad(io::Error::from(io::ErrorKind::UnexpectedEof)),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn read_nonempty(self, n: usize) -> Io<Option<Vec<u8>>, T> {
+        match self.read(n) {
+            Good(buf, r) => if buf.is_empty() { Good(None, r) } else { Good(Some(buf), r) },
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn read_frame(self) -> Io<Vec<u8>, T> {
+        match self {
+            Good(_, mut r) => {
+                match read_frame(&mut r) {
+                    Ok(buf) => Good(buf, r),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
     pub fn read_to_end(self) -> Io<Vec<u8>, T> {
         match self {
             Good(_, mut r) => {
@@ -40,6 +232,66 @@ impl<A, T> Io<A, T> where T: Read {
         }
     }
 
+    pub fn read_to_end_progress<F: FnMut(usize)>(self, on_progress: F) -> Io<Vec<u8>, T> {
+        match self {
+            Good(_, mut r) => {
+                match read_to_end_progress(&mut r, on_progress) {
+                    Ok(buf) => Good(buf, r),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn chunks(self, size: usize) -> io::Result<Chunks<T>> {
+        match self {
+            Good(_, r) => iter::chunks(r, size),
+            Bad(err) => Err(err),
+        }
+    }
+
+    pub fn spool_to<W: Write>(self, dst: &mut W, chunk: usize) -> Io<u64, T> {
+        match self {
+            Good(_, mut r) => {
+                match spool(&mut r, dst, chunk) {
+                    Ok(n) => Good(n, r),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn count_byte(self, byte: u8) -> Io<usize, T> {
+        match self {
+            Good(_, mut r) => {
+                match count_byte(&mut r, byte) {
+                    Ok(n) => Good(n, r),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn read_to_end_limited(self, max: usize) -> Io<Vec<u8>, T> {
+        match self {
+            Good(_, mut r) => {
+                let mut buf = Vec::new();
+                match r.by_ref().take((max as u64).saturating_add(1)).read_to_end(&mut buf) {
+                    Ok(n) if n > max => {
+                        let msg = "stream exceeds the maximum length";
+                        Bad(io::Error::new(io::ErrorKind::InvalidData, msg))
+                    }
+                    Ok(..) => Good(buf, r),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
     pub fn read_to_string(self) -> Io<String, T> {
         match self {
             Good(_, mut r) => {
@@ -82,66 +334,66 @@ impl<A, T> Io<A, T> where T: Write {
         }
     }
 
-    pub fn write_fmt(self, fmt: fmt::Arguments) -> Io<(), T> {
-        match self {
-            Good(_, mut w) => {
-                match w.write_fmt(fmt) {
-                    Ok(..) => Good((), w), 
-                    Err(err) => Bad(err),
+    pub fn write_exact(self, buf: &[u8]) -> Io<(), T> {
+        match self.write(buf) {
+            Good(n, w) => {
+                if n == buf.len() {
+                    Good((), w)
+                } else {
+                    let msg = format!("short write: wrote {} of {} bytes", n, buf.len());
+                    Bad(io::Error::new(io::ErrorKind::WriteZero, msg))
                 }
             }
             Bad(err) => Bad(err)
         }
     }
 
-}
-
-impl<A, T> Io<A, T> where T: Seek {
-
-    pub fn seek(self, pos: io::SeekFrom) -> Io<u64, T> {
+    pub fn write_chunks<I, C>(self, chunks: I) -> Io<(), T>
+            where I: IntoIterator<Item = C>, C: AsRef<[u8]> {
         match self {
-            Good(_, mut s) => {
-                match s.seek(pos) {
-                    Ok(n) => Good(n, s),
-                    Err(err) => Bad(err),
+            Good(_, mut w) => {
+                for chunk in chunks {
+                    if let Err(err) = w.write_all(chunk.as_ref()) { return Bad(err) }
                 }
+                Good((), w)
             }
             Bad(err) => Bad(err)
         }
     }
 
-}
-
-impl<A, T> Io<A, T> where T: BufRead {
-
-    pub fn fill_buf(self) -> Io<(), T> {
+    pub fn write_joined<I, S, D>(self, items: I, sep: D) -> Io<(), T>
+            where I: IntoIterator<Item = S>, S: AsRef<[u8]>, D: AsRef<[u8]> {
         match self {
-            Good(_, mut r) => {
-                match r.fill_buf() {
-                    Ok(..) => Good((), r),
-                    Err(err) => Bad(err),
+            Good(_, mut w) => {
+                for (i, item) in items.into_iter().enumerate() {
+                    if i > 0 {
+                        if let Err(err) = w.write_all(sep.as_ref()) { return Bad(err) }
+                    }
+                    if let Err(err) = w.write_all(item.as_ref()) { return Bad(err) }
                 }
+                Good((), w)
             }
             Bad(err) => Bad(err)
         }
     }
 
-    pub fn consume(self, amt: usize) -> Io<(), T> {
+    pub fn append_reader<R: Read>(self, mut src: R) -> Io<u64, T> {
         match self {
-            Good(_, mut r) => {
-                r.consume(amt);
-                Good((), r)
+            Good(_, mut w) => {
+                match io::copy(&mut src, &mut w) {
+                    Ok(n) => Good(n, w),
+                    Err(err) => Bad(err),
+                }
             }
             Bad(err) => Bad(err)
         }
     }
 
-    pub fn read_until(self, byte: u8) -> Io<Vec<u8>, T> {
+    pub fn write_all_retry(self, buf: &[u8], retries: usize) -> Io<(), T> {
         match self {
-            Good(_, mut r) => {
-                let mut buf = Vec::new();
-                match r.read_until(byte, &mut buf) {
-                    Ok(..) => Good(buf, r),
+            Good(_, mut w) => {
+                match write_all_retry(&mut w, buf, retries) {
+                    Ok(..) => Good((), w),
                     Err(err) => Bad(err),
                 }
             }
@@ -149,12 +401,11 @@ impl<A, T> Io<A, T> where T: BufRead {
         }
     }
 
-    pub fn read_line(self) -> Io<String, T> {
+    pub fn write_fmt(self, fmt: fmt::Arguments) -> Io<(), T> {
         match self {
-            Good(_, mut r) => {
-                let mut buf = String::new();
-                match r.read_line(&mut buf) {
-                    Ok(..) => Good(buf, r),
+            Good(_, mut w) => {
+                match w.write_fmt(fmt) {
+                    Ok(..) => Good((), w), 
                     Err(err) => Bad(err),
                 }
             }
@@ -162,30 +413,32 @@ impl<A, T> Io<A, T> where T: BufRead {
         }
     }
 
-    pub fn split(self, byte: u8) -> io::Result<io::Split<T>> {
+    pub fn write_fmt_counted(self, fmt: fmt::Arguments) -> Io<usize, T> {
         match self {
-            Good(_, r) => Ok(r.split(byte)),
-            Bad(err) => Err(err)
+            Good(_, mut w) => {
+                let buf = fmt::format(fmt);
+                match w.write_all(buf.as_bytes()) {
+                    Ok(..) => Good(buf.len(), w),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
         }
     }
 
-    pub fn lines(self) -> io::Result<io::Lines<T>> {
-        match self {
-            Good(_, r) => Ok(r.lines()),
-            Bad(err) => Err(err),
-        }
+    pub fn write_crlf_line(self, s: &str) -> Io<(), T> {
+        self.write_all(s.as_bytes()).write_all(b"\r\n")
     }
 
-}
+    pub fn write_str(self, s: &str) -> Io<usize, T> { self.write(s.as_bytes()) }
 
-impl<A> Io<A, RealWorld> {
+    pub fn write_str_all(self, s: &str) -> Io<(), T> { self.write_all(s.as_bytes()) }
 
-    pub fn print_line(self, buf: &str) -> Io<(), RealWorld> {
+    pub fn flush(self) -> Io<(), T> {
         match self {
-            Good(_, rw) => {
-                let result = rw.stdout.lock().write_all(format!("{}\n", buf).as_bytes());
-                match result {
-                    Ok(..) => Good((), rw),
+            Good(_, mut w) => {
+                match w.flush() {
+                    Ok(..) => Good((), w),
                     Err(err) => Bad(err),
                 }
             }
@@ -193,25 +446,51 @@ impl<A> Io<A, RealWorld> {
         }
     }
 
-    pub fn read_line(self) -> Io<String, RealWorld> {
+    pub fn write_display<D: fmt::Display + ?Sized>(self, value: &D) -> Io<(), T> {
+        self.write_fmt(format_args!("{}", value))
+    }
+
+    pub fn write_display_line<D: fmt::Display + ?Sized>(self, value: &D) -> Io<(), T> {
+        self.write_fmt(format_args!("{}\n", value))
+    }
+
+    pub fn flush_on_drop(self) -> Io<A, FlushOnDrop<T>> { self.map_handle(FlushOnDrop::new) }
+
+    pub fn prefix(self, prefix: String) -> Io<A, PrefixWriter<T>> {
+        self.map_handle(|w| PrefixWriter::new(w, prefix))
+    }
+
+    #[cfg(feature = "json")]
+    pub fn write_json<V>(self, value: &V) -> Io<(), T> where V: serde::Serialize {
         match self {
-            Good(_, mut rw) => {
-                let mut buf = String::new();
-                match rw.stdin.read_line(&mut buf) {
-                    Ok(..) => Good(buf, rw),
-                    Err(err) => Bad(err),
+            Good(_, mut w) => {
+                match serde_json::to_writer(&mut w, value) {
+                    Ok(..) => Good((), w),
+                    Err(err) => Bad(json_error(err)),
                 }
             }
             Bad(err) => Bad(err)
         }
     }
 
-    pub fn write_to_err(self, buf: &[u8]) -> Io<usize, RealWorld> {
+    pub fn convert_line_endings(self, ending: LineEnding) -> Io<A, LineEndingWriter<T>> {
+        self.map_handle(|w| LineEndingWriter::new(w, ending))
+    }
+
+    #[cfg(feature = "base64")]
+    pub fn base64_encode(self) -> Io<A, Base64Writer<T>> {
+        self.map_handle(Base64Writer::new)
+    }
+
+}
+
+impl<A, T> Io<A, T> where T: Seek {
+
+    pub fn seek(self, pos: io::SeekFrom) -> Io<u64, T> {
         match self {
-            Good(_, rw) => {
-                let result = rw.stderr.lock().write(buf);
-                match result {
-                    Ok(n) => Good(n, rw),
+            Good(_, mut s) => {
+                match s.seek(pos) {
+                    Ok(n) => Good(n, s),
                     Err(err) => Bad(err),
                 }
             }
@@ -219,12 +498,11 @@ impl<A> Io<A, RealWorld> {
         }
     }
 
-    pub fn write_all_to_err(self, buf: &[u8]) -> Io<(), RealWorld> {
+    pub fn stream_len(self) -> Io<u64, T> {
         match self {
-            Good(_, rw) => {
-                let result = rw.stderr.lock().write_all(buf);
-                match result {
-                    Ok(..) => Good((), rw),
+            Good(_, mut s) => {
+                match stream_len(&mut s) {
+                    Ok(len) => Good(len, s),
                     Err(err) => Bad(err),
                 }
             }
@@ -232,10 +510,908 @@ impl<A> Io<A, RealWorld> {
         }
     }
 
-    pub fn write_fmt_to_err(self, fmt: fmt::Arguments) -> Io<(), RealWorld> {
+}
+
+impl<A, T> Io<A, T> where T: Seek + Read {
+
+    pub fn seek_read(self, pos: io::SeekFrom, n: usize) -> Io<Vec<u8>, T> {
+        self.seek(pos).read(n)
+    }
+
+}
+
+impl<A, T> Io<A, T> where T: Seek + Write {
+
+    pub fn seek_write_all(self, pos: io::SeekFrom, buf: &[u8]) -> Io<(), T> {
+        self.seek(pos).write_all(buf)
+    }
+
+}
+
+impl<A, T> Io<A, T> where T: BufRead {
+
+    pub fn fill_buf(self) -> Io<(), T> {
         match self {
-            Good(_, rw) => {
-                let result = rw.stderr.lock().write_fmt(fmt);
+            Good(_, mut r) => {
+                match r.fill_buf() {
+                    Ok(..) => Good((), r),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn peek_byte(self) -> Io<Option<u8>, T> {
+        match self {
+            Good(_, mut r) => {
+                let byte = match r.fill_buf() {
+                    Ok(buf) => buf.first().cloned(),
+                    Err(err) => return Bad(err),
+                };
+                Good(byte, r)
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn consume(self, amt: usize) -> Io<(), T> {
+        match self {
+            Good(_, mut r) => {
+                r.consume(amt);
+                Good((), r)
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn read_until(self, byte: u8) -> Io<Vec<u8>, T> {
+        match self {
+            Good(_, mut r) => {
+                let mut buf = Vec::new();
+                match r.read_until(byte, &mut buf) {
+                    Ok(..) => Good(buf, r),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn read_until_excl(self, byte: u8) -> Io<Vec<u8>, T> {
+        match self.read_until(byte) {
+            Good(mut buf, r) => {
+                if buf.last() == Some(&byte) { buf.pop(); }
+                Good(buf, r)
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn read_until_bytes(self, delim: &[u8]) -> Io<Vec<u8>, T> {
+        match self {
+            Good(_, mut r) => {
+                let mut buf = Vec::new();
+                match read_until_bytes(&mut r, delim, &mut buf) {
+                    Ok(..) => Good(buf, r),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn read_line(self) -> Io<String, T> {
+        match self {
+            Good(_, mut r) => {
+                let mut buf = String::new();
+                match r.read_line(&mut buf) {
+                    Ok(..) => Good(buf, r),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn read_line_opt(self) -> Io<Option<String>, T> {
+        match self {
+            Good(_, mut r) => {
+                let mut buf = String::new();
+                match r.read_line(&mut buf) {
+                    Ok(0) => Good(None, r),
+                    Ok(..) => Good(Some(buf), r),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn read_paragraph(self) -> Io<String, T> {
+        match self {
+            Good(_, mut r) => {
+                match read_paragraph(&mut r) {
+                    Ok(buf) => Good(buf, r),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn read_line_limited(self, max: usize) -> Io<String, T> {
+        match self {
+            Good(_, mut r) => {
+                let mut buf = Vec::new();
+                match read_until_limited(&mut r, b'\n', max, &mut buf) {
+                    Ok(true) => {
+                        match String::from_utf8(buf) {
+                            Ok(line) => Good(line, r),
+                            Err(err) => Bad(io::Error::new(io::ErrorKind::InvalidData, err)),
+                        }
+                    }
+                    Ok(false) => {
+                        let msg = "line exceeds the maximum length";
+                        Bad(io::Error::new(io::ErrorKind::InvalidData, msg))
+                    }
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn read_record(self, terminator: u8, max: usize) -> Io<Vec<u8>, T> {
+        match self {
+            Good(_, mut r) => {
+                let mut buf = Vec::new();
+                let limit = (max as u64).saturating_add(1);
+                match r.by_ref().take(limit).read_until(terminator, &mut buf) {
+                    Ok(..) => {
+                        if buf.last() == Some(&terminator) { buf.pop(); }
+                        if buf.len() > max {
+                            let msg = "record exceeds the maximum length";
+                            Bad(io::Error::new(io::ErrorKind::InvalidData, msg))
+                        } else {
+                            Good(buf, r)
+                        }
+                    }
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn read_line_into(self, mut buf: String) -> Io<String, T> {
+        match self {
+            Good(_, mut r) => {
+                match r.read_line(&mut buf) {
+                    Ok(..) => Good(buf, r),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn read_crlf_line(self) -> Io<String, T> {
+        match self.read_until(b'\n') {
+            Good(mut line, r) => {
+                trim_line_ending(&mut line);
+                match String::from_utf8(line) {
+                    Ok(line) => Good(line, r),
+                    Err(err) => Bad(io::Error::new(io::ErrorKind::InvalidData, err)),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn read_pair(self, delim: u8) -> Io<(Vec<u8>, Vec<u8>), T> {
+        match self.read_until(b'\n') {
+            Good(mut line, r) => {
+                trim_line_ending(&mut line);
+                match line.iter().position(|&b| b == delim) {
+                    Some(i) => {
+                        let second = line[i + 1..].to_vec();
+                        line.truncate(i);
+                        Good((line, second), r)
+                    }
+                    None => {
+                        let msg = "delimiter not found in line";
+                        Bad(io::Error::new(io::ErrorKind::InvalidData, msg))
+                    }
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn read_csv_row(self, sep: u8) -> Io<Vec<String>, T> {
+        match self.read_until(b'\n') {
+            Good(mut line, r) => {
+                if line.is_empty() { return Good(Vec::new(), r) }
+                trim_line_ending(&mut line);
+                let fields = line.split(|&b| b == sep).map(|field| {
+                    String::from_utf8(field.to_vec())
+                }).collect();
+                match fields {
+                    Ok(fields) => Good(fields, r),
+                    Err(err) => Bad(io::Error::new(io::ErrorKind::InvalidData, err)),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn read_all_lines(self) -> Io<Vec<String>, T> {
+        match self {
+            Good(_, mut r) => {
+                match r.by_ref().lines().collect() {
+                    Ok(lines) => Good(lines, r),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn unique_lines(self) -> Io<Vec<String>, T> {
+        self.try_fold_lines(Vec::new(), |mut lines: Vec<String>, line| {
+            if lines.last() != Some(&line) { lines.push(line); }
+            Ok(lines)
+        })
+    }
+
+    pub fn all_unique_lines(self) -> Io<Vec<String>, T> {
+        let mut seen = HashSet::new();
+        self.try_fold_lines(Vec::new(), |mut lines, line| {
+            if seen.insert(line.clone()) { lines.push(line); }
+            Ok(lines)
+        })
+    }
+
+    pub fn try_fold_lines<B, F>(self, init: B, mut f: F) -> Io<B, T>
+            where F: FnMut(B, String) -> io::Result<B> {
+        match self {
+            Good(_, mut r) => {
+                let mut acc = init;
+                for line in r.by_ref().lines() {
+                    let result = line.and_then(|line| f(acc, line));
+                    match result {
+                        Ok(next) => acc = next,
+                        Err(err) => return Bad(err),
+                    }
+                }
+                Good(acc, r)
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn split(self, byte: u8) -> io::Result<io::Split<T>> {
+        match self {
+            Good(_, r) => Ok(r.split(byte)),
+            Bad(err) => Err(err)
+        }
+    }
+
+    pub fn lines(self) -> io::Result<io::Lines<T>> {
+        match self {
+            Good(_, r) => Ok(r.lines()),
+            Bad(err) => Err(err),
+        }
+    }
+
+    pub fn words(self) -> io::Result<Words<T>> {
+        match self {
+            Good(_, r) => Ok(iter::words(r)),
+            Bad(err) => Err(err),
+        }
+    }
+
+}
+
+#[cfg(feature = "base64")]
+impl<A, T> Io<A, Base64Writer<T>> where T: Write {
+
+    pub fn base64_finish(self) -> Io<(), T> {
+        match self {
+            Good(_, w) => {
+                match w.finish() {
+                    Ok(w) => Good((), w),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+}
+
+impl<'a, A> Io<A, io::Cursor<&'a [u8]>> {
+
+    pub fn read_to_cow(self) -> Io<Cow<'a, str>, io::Cursor<&'a [u8]>> {
+        match self {
+            Good(_, mut c) => {
+                let bytes = *c.get_ref();
+                let start = cmp::min(c.position(), bytes.len() as u64) as usize;
+                c.set_position(bytes.len() as u64);
+                Good(String::from_utf8_lossy(&bytes[start..]), c)
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+}
+
+impl<A> Io<A, fs::File> {
+
+    pub fn set_permissions(self, perm: fs::Permissions) -> Io<(), fs::File> {
+        match self {
+            Good(_, f) => {
+                match f.set_permissions(perm) {
+                    Ok(..) => Good((), f),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+}
+
+impl<A> Io<A, net::TcpStream> {
+
+    pub fn read_line_timeout(self, dur: Duration) -> Io<Option<String>, net::TcpStream> {
+        match self {
+            Good(_, s) => {
+                if dur == Duration::from_secs(0) {
+                    let msg = "cannot read a line with a zero timeout";
+                    return Bad(io::Error::new(io::ErrorKind::InvalidInput, msg))
+                }
+                match read_line_timeout(&s, Instant::now() + dur) {
+                    Ok(line) => Good(line, s),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn split_halves(self) -> io::Result<(ReadHalf, WriteHalf)> {
+        match self {
+            Good(_, s) => halves::split(s),
+            Bad(err) => Err(err),
+        }
+    }
+
+    pub fn set_deadline(self, at: Instant) -> Io<(), DeadlineStream> {
+        match self {
+            Good(_, s) => Good((), DeadlineStream::new(s, at)),
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn has_data(self) -> Io<bool, net::TcpStream> {
+        match self {
+            Good(_, s) => {
+                match sys::has_data(&s) {
+                    Ok(ready) => Good(ready, s),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn is_read_ready(&self, timeout: Duration) -> io::Result<bool> {
+        match *self {
+            Good(_, ref s) => sys::is_read_ready(s, timeout),
+            Bad(ref err) => Err(copy_error(err)),
+        }
+    }
+
+    pub fn set_nodelay(self, on: bool) -> Io<(), net::TcpStream> {
+        match self {
+            Good(_, s) => {
+                match s.set_nodelay(on) {
+                    Ok(..) => Good((), s),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn set_ttl(self, ttl: u32) -> Io<(), net::TcpStream> {
+        match self {
+            Good(_, s) => {
+                match s.set_ttl(ttl) {
+                    Ok(..) => Good((), s),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    #[cfg(unix)]
+    pub fn set_keepalive(self, on: bool) -> Io<(), net::TcpStream> {
+        match self {
+            Good(_, s) => {
+                match sys::set_keepalive(&s, on) {
+                    Ok(..) => Good((), s),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+}
+
+impl<A> Io<A, net::UdpSocket> {
+
+    pub fn set_broadcast(self, on: bool) -> Io<(), net::UdpSocket> {
+        match self {
+            Good(_, s) => {
+                match s.set_broadcast(on) {
+                    Ok(..) => Good((), s),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn join_multicast_v4(self, multiaddr: &net::Ipv4Addr, interface: &net::Ipv4Addr)
+            -> Io<(), net::UdpSocket> {
+        match self {
+            Good(_, s) => {
+                match s.join_multicast_v4(multiaddr, interface) {
+                    Ok(..) => Good((), s),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn leave_multicast_v4(self, multiaddr: &net::Ipv4Addr, interface: &net::Ipv4Addr)
+            -> Io<(), net::UdpSocket> {
+        match self {
+            Good(_, s) => {
+                match s.leave_multicast_v4(multiaddr, interface) {
+                    Ok(..) => Good((), s),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+}
+
+fn write_all_retry<W: Write>(w: &mut W, mut buf: &[u8], mut retries: usize) -> io::Result<()> {
+    while !buf.is_empty() {
+        match w.write(buf) {
+            Ok(0) => {
+                let msg = "failed to write whole buffer";
+                return Err(io::Error::new(io::ErrorKind::WriteZero, msg))
+            }
+            Ok(n) => buf = &buf[n..],
+            Err(ref err) if retries > 0 && err.kind() == io::ErrorKind::Interrupted => {
+                retries -= 1;
+            }
+            Err(ref err) if retries > 0 && err.kind() == io::ErrorKind::WouldBlock => {
+                retries -= 1;
+                thread::sleep(Duration::from_millis(RETRY_SLEEP_MS));
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
+
+fn read_to_end_progress<R, F>(r: &mut R, mut on_progress: F) -> io::Result<Vec<u8>>
+        where R: Read, F: FnMut(usize) {
+    let mut buf = Vec::new();
+    let mut chunk = [0; CHUNK_SIZE];
+    loop {
+        match r.read(&mut chunk) {
+            Ok(0) => {
+                on_progress(buf.len());
+                return Ok(buf)
+            }
+            Ok(n) => {
+                buf.extend_from_slice(&chunk[..n]);
+                on_progress(buf.len());
+            }
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+fn spool<R: Read, W: Write>(r: &mut R, dst: &mut W, chunk: usize) -> io::Result<u64> {
+    if chunk == 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "chunk size must not be zero"))
+    }
+    let mut total = 0;
+    let mut buf = vec![0; chunk];
+    loop {
+        match r.read(&mut buf) {
+            Ok(0) => return Ok(total),
+            Ok(n) => {
+                dst.write_all(&buf[..n])?;
+                total += n as u64;
+            }
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+fn count_byte<R: Read>(r: &mut R, byte: u8) -> io::Result<usize> {
+    let mut count = 0;
+    let mut chunk = [0; CHUNK_SIZE];
+    loop {
+        match r.read(&mut chunk) {
+            Ok(0) => return Ok(count),
+            Ok(n) => count += chunk[..n].iter().filter(|&&b| b == byte).count(),
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+fn read_frame<R: Read>(r: &mut R) -> io::Result<Vec<u8>> {
+    let mut header = [0; 4];
+    r.read_exact(&mut header)?;
+    let len = u32::from_be_bytes(header) as u64;
+    // Read through `take` rather than into a buffer of `len` bytes, so that a bogus length
+    // can't allocate more memory than the data actually received.
+    let mut buf = Vec::new();
+    r.by_ref().take(len).read_to_end(&mut buf)?;
+    if buf.len() as u64 == len {
+        Ok(buf)
+    } else {
+        Err(io::Error::new(io::ErrorKind::UnexpectedEof, "frame ended before its length"))
+    }
+}
+
+/// Reads until the whole of `delim` (which is included) has been read, or EOF.
+fn read_until_bytes<R: BufRead>(r: &mut R, delim: &[u8], buf: &mut Vec<u8>) -> io::Result<()> {
+    let last = match delim.last() {
+        Some(&last) => last,
+        None => return Ok(()),
+    };
+    loop {
+        let (used, done) = {
+            let available = match r.fill_buf() {
+                Ok(available) => available,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            if available.is_empty() { return Ok(()) }
+            let mut used = available.len();
+            let mut done = false;
+            for (i, &b) in available.iter().enumerate() {
+                buf.push(b);
+                if b == last && buf.ends_with(delim) {
+                    used = i + 1;
+                    done = true;
+                    break;
+                }
+            }
+            (used, done)
+        };
+        r.consume(used);
+        if done { return Ok(()) }
+    }
+}
+
+/// Reads lines until a blank line or EOF, returning them without the blank line.
+fn read_paragraph<R: BufRead>(r: &mut R) -> io::Result<String> {
+    let mut buf = String::new();
+    loop {
+        let start = buf.len();
+        if r.read_line(&mut buf)? == 0 { return Ok(buf) }
+        if matches!(&buf[start..], "\n" | "\r\n") {
+            buf.truncate(start);
+            return Ok(buf)
+        }
+    }
+}
+
+/// Reads until `byte` (which is included) or EOF, unless that would be more than `max` bytes.
+/// Returns false if the limit was hit.
+fn read_until_limited<R: BufRead>(r: &mut R, byte: u8, max: usize, buf: &mut Vec<u8>)
+        -> io::Result<bool> {
+    r.by_ref().take(max as u64).read_until(byte, buf)?;
+    if buf.len() < max || buf.last() == Some(&byte) { return Ok(true) }
+    Ok(r.fill_buf()?.is_empty())
+}
+
+fn trim_line_ending(line: &mut Vec<u8>) {
+    if line.last() == Some(&b'\n') {
+        line.pop();
+        if line.last() == Some(&b'\r') { line.pop(); }
+    }
+}
+
+/// Converts a JSON error to an IO error, keeping errors which came from the handle as they are.
+#[cfg(feature = "json")]
+fn json_error(err: serde_json::Error) -> io::Error {
+    if err.is_io() {
+        io::Error::from(err)
+    } else {
+        io::Error::new(io::ErrorKind::InvalidData, err)
+    }
+}
+
+fn stream_len<S: Seek>(s: &mut S) -> io::Result<u64> {
+    let pos = s.stream_position()?;
+    let len = s.seek(io::SeekFrom::End(0))?;
+    if len != pos {
+        s.seek(io::SeekFrom::Start(pos))?;
+    }
+    Ok(len)
+}
+
+fn write_hexdump<W: Write>(out: &mut W, bytes: &[u8]) -> io::Result<()> {
+    for (row, chunk) in bytes.chunks(16).enumerate() {
+        write!(out, "{:08x} ", row * 16)?;
+        for i in 0..16 {
+            if i % 8 == 0 { write!(out, " ")?; }
+            match chunk.get(i) {
+                Some(b) => write!(out, "{:02x} ", b)?,
+                None => write!(out, "   ")?,
+            }
+        }
+        write!(out, " |")?;
+        for &b in chunk {
+            let c = if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' };
+            write!(out, "{}", c)?;
+        }
+        writeln!(out, "|")?;
+    }
+    writeln!(out, "{:08x}", bytes.len())
+}
+
+/// Reads a line which is complete by `deadline`, or returns `None` if none of it has arrived by
+/// then. Data is peeked at before it is read, so nothing past the newline is consumed, and
+/// readiness is waited for instead of setting a read timeout, so a nonblocking socket waits too.
+fn read_line_timeout(mut s: &net::TcpStream, deadline: Instant) -> io::Result<Option<String>> {
+    let mut line = Vec::new();
+    let mut buf = [0; CHUNK_SIZE];
+    loop {
+        let remaining = match deadline.checked_duration_since(Instant::now()) {
+            Some(remaining) if remaining > Duration::from_secs(0) => remaining,
+            _ if line.is_empty() => return Ok(None),
+            _ => {
+                let msg = "timed out partway through a line";
+                return Err(io::Error::new(io::ErrorKind::TimedOut, msg))
+            }
+        };
+        if !sys::is_read_ready(s, remaining)? {
+            // Off unix, a nonblocking socket is never waited for, so don't spin.
+            thread::sleep(cmp::min(remaining, Duration::from_millis(1)));
+            continue
+        }
+        let n = match s.peek(&mut buf) {
+            Ok(n) => n,
+            Err(ref err) if matches!(err.kind(), io::ErrorKind::WouldBlock |
+                                                 io::ErrorKind::Interrupted) => continue,
+            Err(err) => return Err(err),
+        };
+        if n == 0 { break }
+        let (len, done) = match buf[..n].iter().position(|&b| b == b'\n') {
+            Some(i) => (i + 1, true),
+            None => (n, false),
+        };
+        // The peeked bytes are waiting in the socket, so this doesn't block.
+        s.read_exact(&mut buf[..len])?;
+        line.extend_from_slice(&buf[..len]);
+        if done { break }
+    }
+    String::from_utf8(line).map(Some).map_err(|err| {
+        io::Error::new(io::ErrorKind::InvalidData, err)
+    })
+}
+
+impl<A> Io<A, ReconnectingStream> {
+
+    pub fn reconnect(self) -> Io<(), ReconnectingStream> {
+        match self {
+            Good(_, s) => {
+                match ReconnectingStream::connect(s.addr()) {
+                    Ok(s) => Good((), s),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => {
+                match reconnect::error_addr(&err) {
+                    Some(addr) => match ReconnectingStream::connect(addr) {
+                        Ok(s) => Good((), s),
+                        Err(err) => Bad(err),
+                    },
+                    None => Bad(err),
+                }
+            }
+        }
+    }
+
+}
+
+impl<A> Io<A, RealWorld> {
+
+    pub fn print_line(self, buf: &str) -> Io<(), RealWorld> {
+        match self {
+            Good(_, mut rw) => {
+                let result = rw.write_all(format!("{}\n", buf).as_bytes());
+                match result {
+                    Ok(..) => Good((), rw),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn read_line(self) -> Io<String, RealWorld> {
+        match self {
+            Good(_, mut rw) => {
+                let mut buf = String::new();
+                match rw.read_line(&mut buf) {
+                    Ok(..) => Good(buf, rw),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn total_in(&self) -> u64 {
+        match *self {
+            Good(_, ref rw) => rw.total_in(),
+            Bad(..) => 0,
+        }
+    }
+
+    pub fn total_out(&self) -> u64 {
+        match *self {
+            Good(_, ref rw) => rw.total_out(),
+            Bad(..) => 0,
+        }
+    }
+
+    pub fn clone_world(&self) -> Io<(), RealWorld> {
+        match *self {
+            Good(_, ref rw) => Good((), rw.clone_world()),
+            Bad(ref err) => Bad(copy_error(err)),
+        }
+    }
+
+    pub fn set_line_buffered(self, on: bool) -> Io<(), RealWorld> {
+        match self {
+            Good(_, mut rw) => {
+                match rw.set_line_buffered(on) {
+                    Ok(..) => Good((), rw),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn buffered_stdin(self) -> Io<(), BufStdin> {
+        match self {
+            Good(_, mut rw) => {
+                match rw.lock_stdin() {
+                    Ok(stdin) => Good((), stdin),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn lock(self) -> Io<(), LockedWorld> {
+        match self {
+            Good(_, mut rw) => {
+                match rw.lock() {
+                    Ok(locked) => Good((), locked),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn for_each_stdin_line<F: FnMut(String)>(self, f: F) -> Io<(), RealWorld> {
+        match self {
+            Good(_, mut rw) => {
+                match rw.for_each_line(f) {
+                    Ok(..) => Good((), rw),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn with_stdout_locked<F>(self, f: F) -> Io<(), RealWorld>
+            where F: FnOnce(Io<(), LockedOut>) -> Io<(), LockedOut> {
+        match self {
+            Good(_, mut rw) => {
+                let out = match rw.lock_stdout() {
+                    Ok(out) => out,
+                    Err(err) => return Bad(err),
+                };
+                match f(Good((), out)) {
+                    Good(_, out) => {
+                        match rw.unlock_stdout(out) {
+                            Ok(..) => Good((), rw),
+                            Err(err) => Bad(err),
+                        }
+                    }
+                    Bad(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn write_to_err(self, buf: &[u8]) -> Io<usize, RealWorld> {
+        match self {
+            Good(_, mut rw) => {
+                let result = rw.err_writer().write(buf);
+                match result {
+                    Ok(n) => Good(n, rw),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn write_all_to_err(self, buf: &[u8]) -> Io<(), RealWorld> {
+        match self {
+            Good(_, mut rw) => {
+                let result = rw.err_writer().write_all(buf);
+                match result {
+                    Ok(..) => Good((), rw),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn print_to_err(self, buf: &str) -> Io<(), RealWorld> {
+        match self {
+            Good(_, mut rw) => {
+                let result = {
+                    let mut stderr = rw.err_writer();
+                    stderr.write_all(buf.as_bytes()).and_then(|_| stderr.flush())
+                };
+                match result {
+                    Ok(..) => Good((), rw),
+                    Err(err) => Bad(err),
+                }
+            }
+            Bad(err) => Bad(err)
+        }
+    }
+
+    pub fn write_fmt_to_err(self, fmt: fmt::Arguments) -> Io<(), RealWorld> {
+        match self {
+            Good(_, mut rw) => {
+                let result = rw.err_writer().write_fmt(fmt);
                 match result {
                     Ok(..) => Good((), rw),
                     Err(err) => Bad(err),
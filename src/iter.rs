@@ -0,0 +1,176 @@
+use std::io::{self, BufRead, Read};
+use std::mem;
+
+use Burrito;
+use iomonad::copy_error;
+use iomonad::Io::*;
+
+/// An iterator over the whitespace separated words of a buffered reader.
+///
+/// This is created by the `words()` method on `Burrito`. Words are separated by any amount of
+/// ASCII whitespace, and may span across refills of the reader's internal buffer.
+pub struct Words<T> {
+    inner: T,
+}
+
+pub fn words<T: BufRead>(inner: T) -> Words<T> {
+    Words { inner }
+}
+
+impl<T: BufRead> Words<T> {
+
+    fn skip_whitespace(&mut self) -> io::Result<bool> {
+        loop {
+            let (skipped, available) = {
+                let buf = self.inner.fill_buf()?;
+                if buf.is_empty() { return Ok(false) }
+                let skipped = buf.iter().take_while(|b| b.is_ascii_whitespace()).count();
+                (skipped, buf.len())
+            };
+            self.inner.consume(skipped);
+            if skipped < available { return Ok(true) }
+        }
+    }
+
+    fn read_word(&mut self) -> io::Result<Vec<u8>> {
+        let mut word = Vec::new();
+        loop {
+            let (used, done) = {
+                let buf = self.inner.fill_buf()?;
+                if buf.is_empty() { return Ok(word) }
+                let used = buf.iter().take_while(|b| !b.is_ascii_whitespace()).count();
+                word.extend_from_slice(&buf[..used]);
+                (used, used < buf.len())
+            };
+            self.inner.consume(used);
+            if done { return Ok(word) }
+        }
+    }
+
+}
+
+impl<T: BufRead> Iterator for Words<T> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<io::Result<String>> {
+        match self.skip_whitespace() {
+            Ok(true) => {}
+            Ok(false) => return None,
+            Err(err) => return Some(Err(err)),
+        }
+        match self.read_word() {
+            Ok(word) => Some(String::from_utf8(word).map_err(|err| {
+                io::Error::new(io::ErrorKind::InvalidData, err)
+            })),
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+/// An iterator over a reader in chunks of a fixed size.
+///
+/// This is created by the `chunks()` method on `Burrito`. Each chunk is `size` bytes long, read
+/// with as many calls to `read` as that takes, except that the last chunk may be shorter, if the
+/// reader runs out in the middle of one. A chunk is never empty: if the reader's length is a
+/// multiple of `size`, there is no shorter chunk at the end.
+///
+/// If a read fails partway through a chunk, the error is yielded, but the bytes already read are
+/// kept: calling `next()` again continues the same chunk, so a reader which times out or would
+/// block can be retried without the chunks being shifted.
+pub struct Chunks<T> {
+    inner: T,
+    size: usize,
+    buf: Vec<u8>,
+    filled: usize,
+    done: bool,
+}
+
+pub fn chunks<T: Read>(inner: T, size: usize) -> io::Result<Chunks<T>> {
+    if size == 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "chunk size must not be zero"))
+    }
+    Ok(Chunks { inner, size, buf: Vec::new(), filled: 0, done: false })
+}
+
+impl<T: Read> Iterator for Chunks<T> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<io::Result<Vec<u8>>> {
+        if self.done { return None }
+        if self.buf.is_empty() { self.buf = vec![0; self.size]; }
+        while self.filled < self.size {
+            match self.inner.read(&mut self.buf[self.filled..]) {
+                Ok(0) => {
+                    self.done = true;
+                    break
+                }
+                Ok(n) => self.filled += n,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Some(Err(err)),
+            }
+        }
+        if self.filled == 0 { return None }
+        let mut chunk = mem::take(&mut self.buf);
+        chunk.truncate(self.filled);
+        self.filled = 0;
+        Some(Ok(chunk))
+    }
+}
+
+/// An iterator-like reader of lines which wraps each line in a `Burrito` around the handle.
+///
+/// This is created by the `line_burritos()` method on `Burrito`. It owns the handle, and lends
+/// it to each `Burrito` returned by `next_line()`, so that the `Burrito` can be used for more IO
+/// on the handle, like writing a reply, before the next line is read. Because each `Burrito`
+/// borrows the handle, it must be dropped before `next_line()` is called again, so this is not
+/// an `Iterator`; use it with `while let` instead. Once the lines are done, `finish()` returns
+/// the handle.
+///
+/// A `Burrito` returned by `next_line()` failing does not affect the handle, which can still be
+/// used for the next line. But if reading a line fails, that line's `Burrito` fails with an
+/// error of the same kind and message, and the handle is dropped: `next_line()` returns `None`
+/// from then on, and `finish()` returns the original error.
+pub struct LineBurritos<T> {
+    inner: Burrito<(), T>,
+    at_eof: bool,
+}
+
+pub fn line_burritos<T: BufRead>(inner: Burrito<(), T>) -> LineBurritos<T> {
+    LineBurritos { inner, at_eof: false }
+}
+
+impl<T: BufRead> LineBurritos<T> {
+
+    /// Reads the next line, without its line ending, and returns it in a `Burrito` borrowing the
+    /// handle. Returns `None` at EOF, or if the handle has failed.
+    pub fn next_line(&mut self) -> Option<Burrito<String, &mut T>> {
+        if self.at_eof { return None }
+        let mut line = String::new();
+        let result = match self.inner.handle_mut() {
+            Some(r) => r.read_line(&mut line),
+            None => return None,
+        };
+        match result {
+            Ok(0) => {
+                self.at_eof = true;
+                None
+            }
+            Ok(..) => {
+                if line.ends_with('\n') {
+                    line.pop();
+                    if line.ends_with('\r') { line.pop(); }
+                }
+                self.inner.handle_mut().map(|r| Burrito(Good(line, r)))
+            }
+            Err(err) => {
+                let copy = copy_error(&err);
+                self.inner = Burrito(Bad(err));
+                Some(Burrito(Bad(copy)))
+            }
+        }
+    }
+
+    /// Returns a `Burrito` wrapping the handle, or the error if reading a line failed.
+    pub fn finish(self) -> Burrito<(), T> { self.inner }
+
+}
@@ -29,9 +29,19 @@
 //! encapsulates the handles to stdin, stdout, stderr. It does not lock these handles; it will
 //! attempt to lock them each time they are written to / read from.
 //!
+//! Output to stdout is buffered by the `RealWorld` following the conventions of C stdio: it is
+//! line buffered if stdout is a terminal, and block buffered otherwise. Buffered output is
+//! flushed before reading from stdin, and when the `RealWorld` is dropped. An error writing to
+//! stdout is returned by the call which flushes the buffer, and a write which fails leaves none
+//! of its bytes buffered, so it can be retried without duplicating anything.
+//!
 //! The `RealWorld` type is not directly accessible except wrapped inside of the `Burrito` returned
 //! by `burrito()`.
 //!
+//! Programs which only write to stderr can use `burrito_err()` instead, which returns a `Burrito`
+//! wrapping a similar type called `StdErrWorld`. This encapsulates only the handle to stderr, so
+//! all of its writes go to stderr.
+//!
 //! # Using the monadic interface
 //!
 //! Every `Burrito` has two type parameters. The `T` type is the type of the IO handle it wraps.
@@ -75,6 +85,12 @@
 //! being used and to open another one, which could possibly be of a different type. It is not
 //! lazy, and the IO handle will actually be opened even if the `Burrito` is in a state of failure.
 //!
+//! ## `and_lazy()`
+//!
+//! The `and_lazy()` method is the lazy version of `and()`. It takes a function which constructs
+//! the alternative `Burrito`, and only calls it if this `Burrito` is not in a state of failure,
+//! so that the new IO handle is not opened unless it will actually be used.
+//!
 //! ## `or_else()`
 //!
 //! The `or_else()` method provides access to the inner error in the event that the `Burrito`
@@ -83,31 +99,161 @@
 //! either create a `Burrito` of the same type (presumably replacing the original `Burrito`) or
 //! else diverge, e.g. by exiting or panicking.
 //!
+//! Exiting with `std::process::exit` does not run destructors, so output still buffered by a
+//! live `RealWorld` is lost. Since stdout is block buffered when it is not a terminal, output
+//! which appears when a program is run by hand can go missing when it is piped; call `flush()`
+//! on the `Burrito` before exiting.
+//!
 //! ## `or()`
 //!
 //! The `or()` method enables replacing the `Burrito` with another of the same types, but does not
 //! provide access to the inner error. It is not lazy, and will actually open the handle even if
 //! the `Burrito` is not in a state of failure.
 
+use std::borrow::Cow;
 use std::convert::AsRef;
 use std::default::Default;
+use std::env;
 use std::fmt;
+use std::fs;
 use std::path::Path;
+use std::sync::mpsc;
+use std::thread;
 use std::io::{self, Read, Write, Seek, BufRead};
-use std::net::ToSocketAddrs;
+use std::net::{self, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "base64")]
+extern crate base64;
+#[cfg(feature = "json")]
+extern crate serde;
+#[cfg(feature = "json")]
+extern crate serde_json;
+#[cfg(unix)]
+extern crate libc;
 
 mod realworld;
 mod iomonad;
 mod constructors;
+mod iter;
+mod adapters;
+mod memory;
+#[cfg(feature = "base64")]
+mod encoding;
+mod reconnect;
+mod deadline;
+mod halves;
+mod sys;
 
-use realworld::RealWorld;
+use realworld::{RealWorld, StdErrWorld};
+pub use realworld::{BufStdin, LockedOut, LockedWorld};
 use iomonad::Io;
 use iomonad::Io::*;
-pub use constructors::{FromPath, FromAddr};
+pub use constructors::{FromPath, FromAddr, NamedFile, NewFile};
+pub use iter::{Chunks, LineBurritos, Words};
+pub use adapters::{FlushOnDrop, Instrumented, LineEnding, LineEndingWriter, PrefixWriter, Quota,
+                   TeeReader};
+pub use memory::ChunkReader;
+#[cfg(feature = "base64")]
+pub use encoding::{Base64Reader, Base64Writer};
+pub use reconnect::ReconnectingStream;
+pub use deadline::DeadlineStream;
+pub use halves::{ReadHalf, WriteHalf};
 
 /// Create a default burrito (wrapping the stdio handles).
 pub fn burrito() -> Burrito<(), RealWorld> { Burrito::default() }
 
+/// Create a burrito which writes only to stderr.
+///
+/// This `Burrito` implements the methods for handles which implement `Write`, all of which write
+/// to stderr, and it has no way to write to stdout.
+///
+/// ```
+/// # extern crate burrito;
+/// # fn main() {
+/// use burrito::burrito_err;
+///
+/// burrito_err().write_display_line("50% done");
+/// # }
+/// ```
+pub fn burrito_err() -> Burrito<(), StdErrWorld> { Burrito::default() }
+
+/// Copies everything from the `src` handle to the `dst` handle until EOF, returning a `Burrito`
+/// wrapping the `dst` handle and holding the number of bytes copied. The `src` handle is dropped.
+/// If either `Burrito` has failed, or reading or writing fails during the copy, the returned
+/// `Burrito` will be in a state of failure; if both have failed, it holds the error from `src`.
+///
+/// ```
+/// # extern crate burrito;
+/// # fn main() {
+/// use std::io::Cursor;
+/// use burrito::{pipe, Burrito};
+///
+/// let src = Burrito::wrap(Ok(Cursor::new("hello")));
+/// let dst = Burrito::wrap(Ok(Vec::new()));
+/// let (n, output) = pipe(src, dst).ok().unwrap();
+/// assert_eq!((n, &output[..]), (5, &b"hello"[..]));
+/// # }
+/// ```
+pub fn pipe<A, B, RT, WT>(src: Burrito<A, RT>, dst: Burrito<B, WT>) -> Burrito<u64, WT>
+        where RT: Read, WT: Write {
+    match (src, dst) {
+        (Burrito(Good(_, mut r)), Burrito(Good(_, mut w))) => {
+            match io::copy(&mut r, &mut w) {
+                Ok(n) => Burrito(Good(n, w)),
+                Err(err) => Burrito(Bad(err)),
+            }
+        }
+        (Burrito(Bad(err)), _) | (_, Burrito(Bad(err))) => Burrito(Bad(err)),
+    }
+}
+
+/// Converts each of the `Burrito`s to a `Result` of its data, as `to_data()` does, and collects
+/// the data of all of them. This short-circuits: the result is the error from the first failed
+/// `Burrito`, and the `Burrito`s after it are dropped without being examined.
+///
+/// ```
+/// # extern crate burrito;
+/// # fn main() {
+/// use burrito::{collect_data, Burrito};
+///
+/// let burritos = vec![Burrito::from_slice(b"a").read_to_string(),
+///                     Burrito::from_slice(b"b").read_to_string()];
+/// assert_eq!(collect_data(burritos).unwrap(), ["a", "b"]);
+/// # }
+/// ```
+pub fn collect_data<A, T>(burritos: Vec<Burrito<A, T>>) -> io::Result<Vec<A>> {
+    burritos.into_iter().map(Burrito::to_data).collect()
+}
+
+/// Separates the `Burrito`s into the contents of those which are good and the errors of those
+/// which have failed, as `ok()` does, preserving their order. Unlike `collect_data`, this examines
+/// every `Burrito`.
+///
+/// ```
+/// # extern crate burrito;
+/// # fn main() {
+/// use std::fs::File;
+/// use burrito::{partition, Burrito};
+///
+/// let burritos = vec![Burrito::wrap(File::open("/foo/bar/baz")).read_to_string(),
+///                     Burrito::wrap(File::open("/foo/bar/qux")).read_to_string()];
+/// let (configs, errors) = partition(burritos);
+/// assert_eq!(configs.len() + errors.len(), 2);
+/// # }
+/// ```
+pub fn partition<A, T>(burritos: Vec<Burrito<A, T>>) -> (Vec<(A, T)>, Vec<io::Error>) {
+    let mut good = Vec::new();
+    let mut bad = Vec::new();
+    for burrito in burritos {
+        match burrito {
+            Burrito(Good(data, io)) => good.push((data, io)),
+            Burrito(Bad(err)) => bad.push(err),
+        }
+    }
+    (good, bad)
+}
+
 /// The fundamental monadic type of the burrito library.
 ///
 /// `Burrito` implements different IO methods depending on the traits implemented by the IO handle
@@ -161,8 +307,8 @@ impl<T> Burrito<(), T> {
 
 }
 
-/// These two functions are constructors for types which can be constructed from paths and socket
-/// addresses.
+/// These functions are constructors for types which can be constructed from paths and socket
+/// addresses, and for in-memory handles.
 impl Burrito<(), ()> {
 
     /// Constructs an IO handle using the path argument, according to that IO handle's
@@ -178,6 +324,29 @@ impl Burrito<(), ()> {
     /// let burrito = Burrito::from_path::<_, File>("/foo/bar/baz");
     /// # }
     /// ```
+    ///
+    /// To create a file which must not already exist, construct a `NewFile` instead of a `File`:
+    ///
+    /// ```rust
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::{Burrito, FromPath, NewFile};
+    ///
+    /// let lock = Burrito::from_path::<_, NewFile>("/foo/bar/app.lock");
+    /// # }
+    /// ```
+    ///
+    /// To have every error from the file say which file it came from, construct a `NamedFile`:
+    ///
+    /// ```rust
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::{Burrito, FromPath, NamedFile};
+    ///
+    /// let err = Burrito::from_path::<_, NamedFile>("/foo/bar/baz").to_handle().err().unwrap();
+    /// assert!(err.to_string().starts_with("/foo/bar/baz: "));
+    /// # }
+    /// ```
     pub fn from_path<P: AsRef<Path>, T: FromPath>(path: P) -> Burrito<(), T> {
         match T::from_path(path) {
             Ok(io) => Burrito(Good((), io)),
@@ -185,6 +354,51 @@ impl Burrito<(), ()> {
         }
     }
 
+    /// Like `from_path`, but returns the error immediately if the IO handle can't be constructed,
+    /// instead of wrapping it in a failed `Burrito`. This suits functions which want to fail fast
+    /// with `?` when opening fails, and only use the `Burrito` for the IO which follows.
+    ///
+    /// ```rust
+    /// # extern crate burrito;
+    /// use std::fs::File;
+    /// use std::io;
+    /// use burrito::Burrito;
+    ///
+    /// fn read_config() -> io::Result<Vec<u8>> {
+    ///     let burrito = Burrito::try_from_path::<_, File>("/foo/bar/baz")?;
+    ///     burrito.read_to_end().to_data()
+    /// }
+    /// # fn main() {
+    /// assert!(read_config().is_err());
+    /// # }
+    /// ```
+    pub fn try_from_path<P: AsRef<Path>, T: FromPath>(path: P) -> io::Result<Burrito<(), T>> {
+        T::from_path(path).map(|io| Burrito(Good((), io)))
+    }
+
+    /// Looks up the environment variable `var` and constructs an IO handle from the path it
+    /// contains, as `from_path` does. If the variable is not set, the `Burrito` will be in a
+    /// state of failure, with an error of the kind `io::ErrorKind::NotFound`.
+    ///
+    /// ```rust
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::fs::File;
+    /// use burrito::{Burrito, FromPath};
+    ///
+    /// let burrito = Burrito::from_env::<File>("CONFIG");
+    /// # }
+    /// ```
+    pub fn from_env<T: FromPath>(var: &str) -> Burrito<(), T> {
+        match env::var_os(var) {
+            Some(path) => Burrito::from_path(path),
+            None => {
+                let msg = format!("environment variable {} is not set", var);
+                Burrito(Bad(io::Error::new(io::ErrorKind::NotFound, msg)))
+            }
+        }
+    }
+
     /// Constructs an IO handle using the addr argument, according to that IO handle's
     /// implementation of FromAddr, then wraps that handle in a `Burrito`. It is a good idea to
     /// type annotate this call to ensure the correct kind of handle is constructed.
@@ -205,13 +419,118 @@ impl Burrito<(), ()> {
         }
     }
 
+    /// Like `from_addr`, but returns the error immediately if the IO handle can't be constructed,
+    /// instead of wrapping it in a failed `Burrito`, so that a failure to connect can be handled
+    /// with `?`.
+    ///
+    /// ```no_run
+    /// # extern crate burrito;
+    /// use std::io;
+    /// use std::net::TcpStream;
+    /// use burrito::Burrito;
+    ///
+    /// fn greet() -> io::Result<()> {
+    ///     let burrito = Burrito::try_from_addr::<_, TcpStream>("localhost:12345")?;
+    ///     burrito.write_all(b"hello\n").close()
+    /// }
+    /// # fn main() {
+    /// greet().unwrap();
+    /// # }
+    /// ```
+    pub fn try_from_addr<A: ToSocketAddrs, T: FromAddr>(addr: A) -> io::Result<Burrito<(), T>> {
+        T::from_addr(addr).map(|io| Burrito(Good((), io)))
+    }
+
+    /// Like `from_addr`, but makes up to `attempts` attempts to construct the IO handle, sleeping
+    /// for `delay` after each failure. The `Burrito` holds the first handle successfully
+    /// constructed, or the error from the final attempt. At least one attempt is always made.
+    ///
+    /// ```no_run
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::net::TcpStream;
+    /// use std::time::Duration;
+    /// use burrito::{Burrito, FromAddr};
+    ///
+    /// let burrito = Burrito::connect_retry::<TcpStream, _>("localhost:12345", 10,
+    ///                                                      Duration::from_millis(500));
+    /// # }
+    /// ```
+    pub fn connect_retry<T, A>(addr: A, attempts: usize, delay: Duration) -> Burrito<(), T>
+            where T: FromAddr, A: ToSocketAddrs + Clone {
+        let mut result = T::from_addr(addr.clone());
+        for _ in 1..attempts {
+            if result.is_ok() { break }
+            thread::sleep(delay);
+            result = T::from_addr(addr.clone());
+        }
+        Burrito::wrap(result)
+    }
+
+    /// Opens a `TcpStream` to `addr`, giving up if the connection can't be established within
+    /// `timeout`. Unlike `from_addr`, this takes a single resolved `SocketAddr`; to connect to a
+    /// host name, resolve it first with `ToSocketAddrs` and try each address in turn.
+    ///
+    /// ```no_run
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::time::Duration;
+    /// use burrito::Burrito;
+    ///
+    /// let addr = "127.0.0.1:12345".parse().unwrap();
+    /// let burrito = Burrito::connect_timeout(addr, Duration::from_secs(5));
+    /// # }
+    /// ```
+    pub fn connect_timeout(addr: net::SocketAddr, timeout: Duration)
+            -> Burrito<(), net::TcpStream> {
+        Burrito::wrap(net::TcpStream::connect_timeout(&addr, timeout))
+    }
+
+    /// Constructs a `Burrito` which reads from a borrowed slice of bytes, without copying them.
+    /// The `Burrito` cannot outlive the slice it borrows.
+    ///
+    /// ```rust
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let bytes = b"key=value\n".to_vec();
+    /// let pair = Burrito::from_slice(&bytes).read_pair(b'=').to_data().unwrap();
+    /// assert_eq!(pair, (b"key".to_vec(), b"value".to_vec()));
+    /// # }
+    /// ```
+    pub fn from_slice(bytes: &[u8]) -> Burrito<(), io::Cursor<&[u8]>> {
+        Burrito(Good((), io::Cursor::new(bytes)))
+    }
+
+    /// Constructs a `Burrito` wrapping a `ChunkReader`, which reads the bytes of each of the
+    /// chunks in turn. This is useful for testing, and for adapting producers of discrete
+    /// messages to the `Read` interface.
+    ///
+    /// ```rust
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let chunks = vec![b"hello ".to_vec(), b"world".to_vec()];
+    /// let text = Burrito::from_chunks(chunks).read_to_string().to_data().unwrap();
+    /// assert_eq!(text, "hello world");
+    /// # }
+    /// ```
+    pub fn from_chunks<I>(chunks: I) -> Burrito<(), ChunkReader>
+            where I: IntoIterator<Item = Vec<u8>>, I::IntoIter: 'static {
+        Burrito(Good((), ChunkReader::new(chunks)))
+    }
+
 }
 
 /// These methods are defined for all `Burrito`s.
 impl<A, T> Burrito<A, T> {
 
     /// Allows you to 'pivot' to a new `Burrito` if this one is good, or to remain in a state of
-    /// failure if this `Burrito` has failed. See the module level documentation for more info.
+    /// failure if this `Burrito` has failed. The alternative is constructed eagerly; see
+    /// `and_lazy` for a version which only opens it when needed. See the module level
+    /// documentation for more info.
     pub fn and<B, U>(self, alternative: Burrito<B, U>) -> Burrito<B, U> {
         match self {
             Burrito(Good(..)) => alternative,
@@ -219,6 +538,26 @@ impl<A, T> Burrito<A, T> {
         }
     }
 
+    /// Like `and`, but takes a function which constructs the new `Burrito`; that function is only
+    /// called if this `Burrito` is good. See the module level documentation for more info.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::fs::File;
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::wrap(File::open("/foo/bar/baz"))
+    ///                      .and_lazy(|| Burrito::wrap(File::open("/foo/bar/qux")));
+    /// # }
+    /// ```
+    pub fn and_lazy<B, U, F>(self, f: F) -> Burrito<B, U> where F: FnOnce() -> Burrito<B, U> {
+        match self {
+            Burrito(Good(..)) => f(),
+            Burrito(Bad(err)) => Burrito(Bad(err)),
+        }
+    }
+
     /// Allows access to data returned by the most recent IO call on this `Burrito`; this function
     /// must return another `Burrito` of some kind or else diverge. See the module level
     /// documentation for more info.
@@ -230,123 +569,1866 @@ impl<A, T> Burrito<A, T> {
         }
     }
 
-    /// Allows you to substitute this `Burrito` for another of the same type if it has gone bad.
-    pub fn or(self, alternative: Burrito<A, T>) -> Burrito<A, T> {
+    /// Applies `f` to each of the items in turn, along with a `Burrito` wrapping the IO handle,
+    /// and collects the data each call returns. Each call to `f` receives the handle returned by
+    /// the call before it. If any call fails, this stops immediately and returns that failure.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let (counts, output) = Burrito::wrap(Ok(Vec::new()))
+    ///     .map_over(vec!["foo", "barbaz"], |s, burrito| burrito.write(s.as_bytes()))
+    ///     .ok().unwrap();
+    /// assert_eq!((counts, output), (vec![3, 6], b"foobarbaz".to_vec()));
+    /// # }
+    /// ```
+    pub fn map_over<I, B, F>(self, items: I, mut f: F) -> Burrito<Vec<B>, T>
+            where I: IntoIterator, F: FnMut(I::Item, Burrito<(), T>) -> Burrito<B, T> {
         match self {
-            Burrito(Bad(..)) => alternative,
-            _ => self,
+            Burrito(Good(_, mut io)) => {
+                let mut results = Vec::new();
+                for item in items {
+                    match f(item, Burrito(Good((), io))) {
+                        Burrito(Good(data, next)) => {
+                            results.push(data);
+                            io = next;
+                        }
+                        Burrito(Bad(err)) => return Burrito(Bad(err)),
+                    }
+                }
+                Burrito(Good(results, io))
+            }
+            Burrito(Bad(err)) => Burrito(Bad(err)),
         }
     }
 
-    /// Allows access to the error thrown if this `Burrito` has gone bad. This function must return
-    /// another `Burrito` of the same type or else diverge. See the module level documentation for
-    /// more info.
-    pub fn or_else<F>(self, f: F) -> Burrito<A, T> 
-            where F: FnOnce(io::Error) -> Burrito<A, T> {
-        match self {
-            Burrito(Bad(err)) => f(err),
-            _ => self
+    /// Runs `f` on this `Burrito` on a new thread, waiting at most `dur` for it to finish. If it
+    /// doesn't finish in time, the `Burrito` fails with an error of the kind
+    /// `io::ErrorKind::TimedOut`. The thread can't be cancelled, so it is left running, and the
+    /// IO handle is lost with it.
+    ///
+    /// Both the handle and the data must be able to move to another thread; handles which are
+    /// not `Send` can't be used with this method.
+    ///
+    /// ```no_run
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::net::TcpStream;
+    /// use std::io::BufReader;
+    /// use std::time::Duration;
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::wrap(TcpStream::connect("localhost:12345").map(BufReader::new))
+    ///                      .timed(Duration::from_secs(5), |burrito| burrito.read_line());
+    /// # }
+    /// ```
+    pub fn timed<B, F>(self, dur: Duration, f: F) -> Burrito<B, T>
+            where F: FnOnce(Burrito<A, T>) -> Burrito<B, T> + Send + 'static,
+                  A: Send + 'static, B: Send + 'static, T: Send + 'static {
+        if let Burrito(Bad(err)) = self { return Burrito(Bad(err)) }
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(f(self));
+        });
+        match rx.recv_timeout(dur) {
+            Ok(burrito) => burrito,
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                Burrito(Bad(io::Error::new(io::ErrorKind::TimedOut, "operation timed out")))
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                Burrito(Bad(io::Error::new(io::ErrorKind::Other, "operation panicked")))
+            }
         }
     }
 
-    /// Drops any data returned by the most recent IO call.
-    pub fn ignore(self) -> Burrito<(), T> {
+    /// The same as `and_then`, except that `f` must return a `Burrito` wrapping the same type of
+    /// handle. When the handle doesn't change, this saves type annotations in long chains where
+    /// the return type of `and_then` can't be inferred.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let payload = Burrito::from_slice(b"\x03abcdef").read(1).bind(|len, burrito| {
+    ///     burrito.read(len[0] as usize)
+    /// });
+    /// assert_eq!(payload.to_data().unwrap(), b"abc");
+    /// # }
+    /// ```
+    pub fn bind<B, F>(self, f: F) -> Burrito<B, T>
+            where F: FnOnce(A, Burrito<(), T>) -> Burrito<B, T> {
+        self.and_then(f)
+    }
+
+    /// Applies `f` to the data returned by the most recent IO call if `cond` is true, leaving the
+    /// data unchanged otherwise. A failed `Burrito` remains failed either way.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let ignore_case = true;
+    /// let line = Burrito::from_slice(b"Hello\n").read_line()
+    ///                                           .map_if(ignore_case, |s| s.to_lowercase());
+    /// assert_eq!(line.to_data().unwrap(), "hello\n");
+    /// # }
+    /// ```
+    pub fn map_if<F>(self, cond: bool, f: F) -> Burrito<A, T> where F: FnOnce(A) -> A {
         match self {
-            Burrito(Good(_, io)) => Burrito(Good((), io)),
-            Burrito(Bad(err)) => Burrito(Bad(err))
+            Burrito(Good(data, io)) if cond => Burrito(Good(f(data), io)),
+            burrito => burrito,
         }
     }
 
-    /// Returns true if the `Burrito` has not failed.
-    pub fn is_good(&self) -> bool {
-        match *self {
-            Burrito(Good(..)) => true,
-            Burrito(Bad(..)) => false,
+    /// Allows you to substitute this `Burrito` for another of the same type if it has gone bad.
+    pub fn or(self, alternative: Burrito<A, T>) -> Burrito<A, T> {
+        match self {
+            Burrito(Bad(..)) if !self.0.is_fused() => alternative,
+            _ => self,
         }
     }
 
-    /// Returns true if the `Burrito` has failed.
-    pub fn is_bad(&self) -> bool { !self.is_good() }
-
-    /// Converts the `Burrito` to a `Result` of both the handle and the most recently returned
-    /// data.
-    pub fn ok(self) -> io::Result<(A, T)> {
+    /// Like `or`, but takes a function which constructs the alternative `Burrito`; that function
+    /// is only called if this `Burrito` has gone bad, so the alternative is not opened unless it
+    /// is needed. Unlike `or_else`, the function does not receive the error.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::fs::File;
+    /// use burrito::Burrito;
+    ///
+    /// let config = Burrito::wrap(File::open("/etc/app/config"))
+    ///                      .or_open(|| Burrito::wrap(File::open("/foo/bar/config")));
+    /// # }
+    /// ```
+    pub fn or_open<F>(self, f: F) -> Burrito<A, T> where F: FnOnce() -> Burrito<A, T> {
         match self {
-            Burrito(Good(data, io)) => Ok((data, io)),
-            Burrito(Bad(err)) => Err(err),
+            Burrito(Bad(..)) if !self.0.is_fused() => f(),
+            _ => self,
         }
     }
 
-    /// Converts the `Burrito` to a `Result` of the most recently returned data.
-    pub fn to_data(self) -> io::Result<A> {
-        match self {
-            Burrito(Good(data, _)) => Ok(data),
-            Burrito(Bad(err)) => Err(err),
+    /// Like `or`, returns this `Burrito` if it is good, and otherwise the alternative. But if both
+    /// have failed, rather than keeping only the alternative's error, the error returned
+    /// describes both failures. It has the same kind as this `Burrito`'s error.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::fs::File;
+    /// use burrito::Burrito;
+    ///
+    /// let config = Burrito::wrap(File::open("/etc/app/config"))
+    ///                      .first_ok(Burrito::wrap(File::open("/foo/bar/config")));
+    /// if let Err(err) = config.to_handle() {
+    ///     println!("no config file could be opened: {}", err);
+    /// }
+    /// # }
+    /// ```
+    pub fn first_ok(self, alternative: Burrito<A, T>) -> Burrito<A, T> {
+        match (self, alternative) {
+            (this, _) if this.0.is_fused() => this,
+            (Burrito(Bad(first)), Burrito(Bad(second))) => {
+                let msg = format!("{}; alternative also failed: {}", first, second);
+                Burrito(Bad(io::Error::new(first.kind(), msg)))
+            }
+            (Burrito(Bad(..)), alternative) => alternative,
+            (this, _) => this,
         }
     }
 
-    /// Converts the `Burrito` to a `Result` of the IO handle wrapped within.
-    pub fn to_handle(self) -> io::Result<T> {
+    /// Allows access to the error thrown if this `Burrito` has gone bad. This function must return
+    /// another `Burrito` of the same type or else diverge. See the module level documentation for
+    /// more info.
+    pub fn or_else<F>(self, f: F) -> Burrito<A, T> 
+            where F: FnOnce(io::Error) -> Burrito<A, T> {
         match self {
-            Burrito(Good(_, io)) => Ok(io),
-            Burrito(Bad(err)) => Err(err),
+            Burrito(Bad(..)) if self.0.is_fused() => self,
+            Burrito(Bad(err)) => f(err),
+            _ => self
         }
     }
 
-}
-
-impl Default for Burrito<(), RealWorld> {
-    fn default() -> Burrito<(), RealWorld> { Burrito(Good((), RealWorld::default())) }
-}
-
-impl<A, T> Burrito<A, T> where T: Read {
-    /// Performs a read on the IO handle inside the burrito. Will read into a buffer of _n_ bytes.
+    /// Like `or_open`, substitutes the `Burrito` constructed by `f` if this one has gone bad, but
+    /// only if its error is of the kind `kind`. A good `Burrito`, or one which failed with an
+    /// error of any other kind, is returned unchanged, so several calls can be chained to handle
+    /// different kinds of error in different ways.
     ///
-    /// Though the buffer passed to `Read::read()` can be stack allocated, this function allocates
-    /// the buffer on the heap, so that its length can be determined by the function call. The
-    /// `Vec<u8>` returned by this type will contain all of the bytes read from the call; if that
-    /// is less than _n_, it will not include any null bytes.
-    pub fn read(self, n: usize) -> Burrito<Vec<u8>, T> { Burrito(self.0.read(n)) }
-    /// Reads to the end of the handle inside the burrito, returning a `Vec<u8>` of bytes.
-    pub fn read_to_end(self) -> Burrito<Vec<u8>, T> { Burrito(self.0.read_to_end()) }
-    /// Reads everything from the handle into a `String`.
-    pub fn read_to_string(self) -> Burrito<String, T> { Burrito(self.0.read_to_string()) }
-}
+    /// ```no_run
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::fs::File;
+    /// use std::io::ErrorKind;
+    /// use burrito::Burrito;
+    ///
+    /// let config = Burrito::wrap(File::open("/etc/app/config"))
+    ///                      .on_kind(ErrorKind::NotFound, || Burrito::wrap(File::create("config")))
+    ///                      .on_kind(ErrorKind::PermissionDenied, || {
+    ///                          Burrito::wrap(File::open("/usr/share/app/config"))
+    ///                      });
+    /// # }
+    /// ```
+    pub fn on_kind<F>(self, kind: io::ErrorKind, f: F) -> Burrito<A, T>
+            where F: FnOnce() -> Burrito<A, T> {
+        match self {
+            Burrito(Bad(ref err)) if err.kind() == kind && !self.0.is_fused() => f(),
+            _ => self,
+        }
+    }
+
+    /// Fuses a failed `Burrito`, so that it can't be recovered: `or`, `or_open`, `first_ok`,
+    /// `or_else` and `on_kind` all return a fused `Burrito` unchanged, even when their alternative
+    /// would have succeeded, and `recover_kind_data` returns its error. The one exception is
+    /// `or_default_data`, which has no way to report the error, and so still returns the default.
+    /// A good `Burrito` is not affected, and if it fails later, that failure is not fused unless
+    /// `fuse` is called again.
+    ///
+    /// The fused error keeps the kind and message of the original, but wraps it, so its
+    /// `raw_os_error()` is `None` and `get_ref()` no longer returns the original inner error.
+    ///
+    /// This is useful for a library which hands `Burrito`s to its callers, but wants failures to
+    /// be final. A fused `Burrito` can't be made good again; to start over, construct a new
+    /// `Burrito`, for example with `Burrito::wrap`, or `reset()` for the stdio `Burrito`.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::fs::File;
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::wrap(File::open("/foo/bar/baz")).fuse()
+    ///                      .or_else(|_| Burrito::wrap(File::open("Cargo.toml")));
+    /// assert!(burrito.is_bad());
+    /// # }
+    /// ```
+    pub fn fuse(self) -> Burrito<A, T> { Burrito(self.0.fuse()) }
+
+    /// Clones the data returned by the most recent IO call, so that the `Burrito` holds a pair of
+    /// copies of it. This is useful when the data is needed both by the next `and_then` and after
+    /// it; it requires that the data implement `Clone`.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let pair = Burrito::from_slice(b"hello\n").read_line().duplicate_data();
+    /// assert_eq!(pair.to_data().unwrap(), ("hello\n".to_string(), "hello\n".to_string()));
+    /// # }
+    /// ```
+    pub fn duplicate_data(self) -> Burrito<(A, A), T> where A: Clone {
+        match self {
+            Burrito(Good(data, io)) => Burrito(Good((data.clone(), data), io)),
+            Burrito(Bad(err)) => Burrito(Bad(err)),
+        }
+    }
+
+    /// Drops any data returned by the most recent IO call.
+    pub fn ignore(self) -> Burrito<(), T> {
+        match self {
+            Burrito(Good(_, io)) => Burrito(Good((), io)),
+            Burrito(Bad(err)) => Burrito(Bad(err))
+        }
+    }
+
+    /// Prints the most recently returned data to stderr using its `Debug` implementation, for
+    /// tracing the data flowing through a chain of calls. Does nothing if the `Burrito` has
+    /// failed. Errors writing to stderr are ignored.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::from_slice(b"hello\n").read_line().debug_print();
+    /// # }
+    /// ```
+    pub fn debug_print(self) -> Burrito<A, T> where A: fmt::Debug {
+        if let Burrito(Good(ref data, _)) = self {
+            let _ = writeln!(io::stderr(), "{:?}", data);
+        }
+        self
+    }
+
+    /// Wraps the IO handle in an `Instrumented` handle, which counts the calls made to its `read`
+    /// and `write` methods.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::wrap(Ok(Vec::new())).instrument().write(b"a").write(b"b");
+    /// assert_eq!(burrito.write_calls(), 2);
+    /// # }
+    /// ```
+    pub fn instrument(self) -> Burrito<A, Instrumented<T>> { Burrito(self.0.instrument()) }
+
+    /// Wraps the IO handle in a `Quota`, which allows no more than `limit` bytes to be read from
+    /// or written to it in total, across any number of calls. Once the quota has been used up,
+    /// further IO fails with an error of the kind `io::ErrorKind::Other` and the message "quota
+    /// exceeded"; an IO call which would go over the quota fails partway through.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::from_slice(b"one\ntwo\nthree\n").with_quota(8)
+    ///                      .read_line().read_line();
+    /// assert_eq!(burrito.to_data().unwrap(), "two\n");
+    /// let burrito = Burrito::from_slice(b"one\ntwo\nthree\n").with_quota(8).read_to_end();
+    /// assert_eq!(burrito.to_data().unwrap_err().to_string(), "quota exceeded");
+    /// let burrito = Burrito::from_slice(b"one\ntwo\n").with_quota(8).read_to_end();
+    /// assert_eq!(burrito.to_data().unwrap(), b"one\ntwo\n");
+    /// # }
+    /// ```
+    pub fn with_quota(self, limit: u64) -> Burrito<A, Quota<T>> {
+        Burrito(self.0.with_quota(limit))
+    }
+
+    /// Replaces the IO handle with the one `f` makes from it, keeping the data, or fails with the
+    /// error `f` returns. This is for wrapping the handle in an adapter which can fail to be
+    /// created.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io::{self, BufReader, Read};
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::from_slice(b"\x1f\x8bcompressed").try_map_handle(|mut r| {
+    ///     let mut magic = [0; 2];
+    ///     r.read_exact(&mut magic)?;
+    ///     if magic != [0x1f, 0x8b] {
+    ///         return Err(io::Error::new(io::ErrorKind::InvalidData, "not gzip"));
+    ///     }
+    ///     Ok(BufReader::new(r))
+    /// });
+    /// assert_eq!(burrito.read_line().to_data().unwrap(), "compressed");
+    /// # }
+    /// ```
+    pub fn try_map_handle<U, F>(self, f: F) -> Burrito<A, U>
+            where F: FnOnce(T) -> io::Result<U> {
+        Burrito(self.0.try_map_handle(f))
+    }
+
+    /// Returns true if the `Burrito` has not failed.
+    pub fn is_good(&self) -> bool {
+        match *self {
+            Burrito(Good(..)) => true,
+            Burrito(Bad(..)) => false,
+        }
+    }
+
+    /// Returns true if the `Burrito` has failed.
+    pub fn is_bad(&self) -> bool { !self.is_good() }
+
+    /// Borrows the IO handle, or returns `None` if the `Burrito` has failed. This is useful for
+    /// calling methods of the handle which the `Burrito` doesn't provide.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::from_slice(b"hello").read(2);
+    /// assert_eq!(burrito.handle_ref().map(|cursor| cursor.position()), Some(2));
+    /// # }
+    /// ```
+    pub fn handle_ref(&self) -> Option<&T> {
+        match *self {
+            Burrito(Good(_, ref io)) => Some(io),
+            Burrito(Bad(..)) => None,
+        }
+    }
+
+    /// Mutably borrows the IO handle, or returns `None` if the `Burrito` has failed.
+    pub fn handle_mut(&mut self) -> Option<&mut T> {
+        match *self {
+            Burrito(Good(_, ref mut io)) => Some(io),
+            Burrito(Bad(..)) => None,
+        }
+    }
+
+    /// Calls `f` with the IO handle, or with `None` if the `Burrito` has failed, and returns the
+    /// `Burrito` unchanged. This is for cleanup which should happen whether or not the `Burrito`
+    /// has failed, but which needs the handle when there is one, like sending a goodbye message
+    /// before closing a connection. `f` runs immediately, so it always runs before the handle is
+    /// dropped, even if the returned `Burrito` is dropped right away.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io::Write;
+    /// use burrito::Burrito;
+    ///
+    /// let output = Burrito::wrap(Ok(Vec::new())).write_all(b"HELLO\n").and_finally(|out| {
+    ///     if let Some(out) = out { let _ = out.write_all(b"BYE\n"); }
+    /// });
+    /// assert_eq!(output.to_handle().unwrap(), b"HELLO\nBYE\n");
+    /// # }
+    /// ```
+    pub fn and_finally<F: FnOnce(Option<&mut T>)>(mut self, f: F) -> Burrito<A, T> {
+        f(self.handle_mut());
+        self
+    }
+
+    /// Runs `f` with a `Burrito` wrapping the IO handle, then drops the handle, returning the
+    /// value `f` computed. `f` returns that value along with the `Burrito` it finished with; if
+    /// that `Burrito` has failed, or this one had already failed, the error is returned instead.
+    /// To flush a writer before it is dropped, use `with_writer`.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let header_len = Burrito::from_slice(b"header\nbody").with(|reader| {
+    ///     let reader = reader.read_line();
+    ///     (reader.handle_ref().map_or(0, |cursor| cursor.position()), reader)
+    /// });
+    /// assert_eq!(header_len.unwrap(), 7);
+    /// # }
+    /// ```
+    pub fn with<R, B, U, F>(self, f: F) -> io::Result<R>
+            where F: FnOnce(Burrito<(), T>) -> (R, Burrito<B, U>) {
+        match self {
+            Burrito(Good(_, io)) => match f(Burrito(Good((), io))) {
+                (result, Burrito(Good(..))) => Ok(result),
+                (_, Burrito(Bad(err))) => Err(err),
+            },
+            Burrito(Bad(err)) => Err(err),
+        }
+    }
+
+    /// Converts the `Burrito` to a `Result` of both the handle and the most recently returned
+    /// data.
+    pub fn ok(self) -> io::Result<(A, T)> {
+        match self {
+            Burrito(Good(data, io)) => Ok((data, io)),
+            Burrito(Bad(err)) => Err(err),
+        }
+    }
+
+    /// Converts the `Burrito` to a `Result` of both the handle and the most recently returned
+    /// data, like `ok()`, but converts the error with `f`. This is useful for leaving the
+    /// `Burrito` in a function which returns its own error type.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io;
+    /// use burrito::Burrito;
+    ///
+    /// enum AppError { Io(io::Error) }
+    ///
+    /// fn read_name() -> Result<String, AppError> {
+    ///     let burrito = Burrito::from_slice(b"ferris").read_to_string();
+    ///     let (name, _) = burrito.into_result(AppError::Io)?;
+    ///     Ok(name)
+    /// }
+    /// # assert!(read_name().is_ok());
+    /// # }
+    /// ```
+    pub fn into_result<E, F: FnOnce(io::Error) -> E>(self, f: F) -> Result<(A, T), E> {
+        match self {
+            Burrito(Good(data, io)) => Ok((data, io)),
+            Burrito(Bad(err)) => Err(f(err)),
+        }
+    }
+
+    /// Converts the `Burrito` to a `Result` of the most recently returned data.
+    pub fn to_data(self) -> io::Result<A> {
+        match self {
+            Burrito(Good(data, _)) => Ok(data),
+            Burrito(Bad(err)) => Err(err),
+        }
+    }
+
+    /// Returns the most recently returned data, or the default value of its type if the
+    /// `Burrito` has failed. The error is discarded.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::fs::File;
+    /// use burrito::Burrito;
+    ///
+    /// let settings = Burrito::wrap(File::open("/foo/bar/baz")).read_to_string().or_default_data();
+    /// assert_eq!(settings, "");
+    /// # }
+    /// ```
+    pub fn or_default_data(self) -> A where A: Default {
+        match self {
+            Burrito(Good(data, _)) => data,
+            Burrito(Bad(..)) => A::default(),
+        }
+    }
+
+    /// Converts the `Burrito` to a `Result` of the most recently returned data, like `to_data()`,
+    /// except that if the `Burrito` has failed with an error of the kind `kind`, it returns
+    /// `value` instead. Errors of any other kind, and fused errors of any kind, are returned as
+    /// they are. The IO handle is discarded.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::fs::File;
+    /// use std::io::ErrorKind;
+    /// use burrito::Burrito;
+    ///
+    /// let config = Burrito::wrap(File::open("/foo/bar/baz")).read_to_string()
+    ///                      .recover_kind_data(ErrorKind::NotFound, String::new());
+    /// assert_eq!(config.unwrap(), "");
+    /// # }
+    /// ```
+    pub fn recover_kind_data(self, kind: io::ErrorKind, value: A) -> io::Result<A> {
+        match self {
+            Burrito(Good(data, _)) => Ok(data),
+            Burrito(Bad(ref err)) if err.kind() == kind && !self.0.is_fused() => Ok(value),
+            Burrito(Bad(err)) => Err(err),
+        }
+    }
+
+    /// Converts the `Burrito` to a `Result` of the IO handle wrapped within.
+    pub fn to_handle(self) -> io::Result<T> {
+        match self {
+            Burrito(Good(_, io)) => Ok(io),
+            Burrito(Bad(err)) => Err(err),
+        }
+    }
+
+    /// Splits the `Burrito` into a `Result` of the IO handle, like `to_handle()`, and the most
+    /// recently returned data, if there is any. A failed `Burrito` holds only its error: the
+    /// handle and data were dropped when the IO call failed, so they can't be reclaimed. The
+    /// data is therefore `Some` exactly when the handle is `Ok`.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let (handle, data) = Burrito::from_slice(b"abc").read(2).take_handle();
+    /// assert_eq!(data.unwrap(), b"ab");
+    /// assert_eq!(handle.unwrap().position(), 2);
+    /// # }
+    /// ```
+    pub fn take_handle(self) -> (io::Result<T>, Option<A>) {
+        match self {
+            Burrito(Good(data, io)) => (Ok(io), Some(data)),
+            Burrito(Bad(err)) => (Err(err), None),
+        }
+    }
+
+}
+
+/// These methods are defined for `Burrito`s holding optional data, such as the result of a read
+/// which may reach EOF.
+impl<A, T> Burrito<Option<A>, T> {
+
+    /// Converts a `Burrito` of optional data to an optional `Burrito`. A `Burrito` holding `Some`
+    /// data becomes `Some` good `Burrito` holding that data, and a failed `Burrito` becomes
+    /// `Some` failed `Burrito`. A `Burrito` holding `None` becomes `None`, and its IO handle is
+    /// dropped; use `and_then` instead if the handle needs to be kept in that case.
+    pub fn transpose(self) -> Option<Burrito<A, T>> {
+        match self {
+            Burrito(Good(Some(data), io)) => Some(Burrito(Good(data, io))),
+            Burrito(Good(None, _)) => None,
+            Burrito(Bad(err)) => Some(Burrito(Bad(err))),
+        }
+    }
+
+}
+
+/// These methods are defined for `Burrito`s wrapping an `Instrumented` handle.
+impl<A, T> Burrito<A, Instrumented<T>> {
+
+    /// The number of calls made to the handle's `read` method, or zero if the `Burrito` has
+    /// failed.
+    pub fn read_calls(&self) -> u64 { self.0.read_calls() }
+
+    /// The number of calls made to the handle's `write` method, or zero if the `Burrito` has
+    /// failed.
+    pub fn write_calls(&self) -> u64 { self.0.write_calls() }
+
+}
+
+impl Default for Burrito<(), RealWorld> {
+    fn default() -> Burrito<(), RealWorld> { Burrito(Good((), RealWorld::default())) }
+}
+
+impl Default for Burrito<(), StdErrWorld> {
+    fn default() -> Burrito<(), StdErrWorld> { Burrito(Good((), StdErrWorld::default())) }
+}
+
+impl<A, T> Burrito<A, T> where T: Read {
+    /// Performs a read on the IO handle inside the burrito. Will read into a buffer of _n_ bytes.
+    ///
+    /// Though the buffer passed to `Read::read()` can be stack allocated, this function allocates
+    /// the buffer on the heap, so that its length can be determined by the function call. The
+    /// `Vec<u8>` returned by this type will contain all of the bytes read from the call; if that
+    /// is less than _n_, it will not include any null bytes.
+    pub fn read(self, n: usize) -> Burrito<Vec<u8>, T> { Burrito(self.0.read(n)) }
+    /// Reads exactly as many bytes as the length of `buf` into it, returning the buffer as the
+    /// data. The `Burrito` fails with an error of the kind `io::ErrorKind::UnexpectedEof` if the
+    /// reader ends before the buffer is filled. Passing the same buffer back in for each read
+    /// avoids allocating a new one every time, unlike `read()`.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::from_slice(b"abcdef").read_exact_into(vec![0; 3])
+    ///                      .bind(|buf, burrito| {
+    ///                          assert_eq!(buf, b"abc");
+    ///                          burrito.read_exact_into(buf)
+    ///                      });
+    /// assert_eq!(burrito.to_data().unwrap(), b"def");
+    /// # }
+    /// ```
+    pub fn read_exact_into(self, buf: Vec<u8>) -> Burrito<Vec<u8>, T> {
+        Burrito(self.0.read_exact_into(buf))
+    }
+    /// Wraps the IO handle in a `TeeReader`, so that everything read from it afterward is also
+    /// written to `sink`. If writing to the sink fails, the read fails with that error.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let mut log = Vec::new();
+    /// let line = Burrito::from_slice(b"hello\nworld\n").tee_read(&mut log).read(6);
+    /// assert_eq!(line.to_data().unwrap(), b"hello\n");
+    /// assert_eq!(log, b"hello\n");
+    /// # }
+    /// ```
+    pub fn tee_read<W: Write>(self, sink: W) -> Burrito<A, TeeReader<T, W>> {
+        Burrito(self.0.tee_read(sink))
+    }
+    /// Wraps the IO handle in a `Base64Reader`, so that everything read from it afterward is
+    /// base64 decoded. Requires the `base64` feature.
+    #[cfg(feature = "base64")]
+    pub fn base64_decode(self) -> Burrito<A, Base64Reader<T>> { Burrito(self.0.base64_decode()) }
+    /// Reads one JSON value from the IO handle and deserializes it, leaving anything after it
+    /// to be read later, so that this can be called repeatedly on a stream of JSON values. If
+    /// the JSON is malformed or doesn't match the type, the `Burrito` fails with an error of the
+    /// kind `io::ErrorKind::InvalidData`, and if the handle is already at EOF it fails with
+    /// `io::ErrorKind::UnexpectedEof`. The handle is read a byte at a time, so it should be
+    /// buffered. Requires the `json` feature.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::from_slice(b"[1, 2]\n[3]\n").read_json::<Vec<u32>>()
+    ///                      .bind(|first, burrito| {
+    ///                          assert_eq!(first, [1, 2]);
+    ///                          burrito.read_json::<Vec<u32>>()
+    ///                      });
+    /// assert_eq!(burrito.to_data().unwrap(), [3]);
+    /// # }
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn read_json<V>(self) -> Burrito<V, T> where V: serde::de::DeserializeOwned {
+        Burrito(self.0.read_json())
+    }
+    /// Performs a read of up to _n_ bytes, like `read()`, but distinguishes EOF: the `Burrito`
+    /// holds `None` if no bytes could be read, and otherwise `Some` of the bytes read, so it will
+    /// never hold `Some` empty `Vec`. _n_ should be greater than zero, as a read into an empty
+    /// buffer can't tell whether EOF has been reached, and will always hold `None`.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io::Cursor;
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::wrap(Ok(Cursor::new("abc"))).read_nonempty(4)
+    ///                      .and_then(|data, burrito| {
+    ///                          assert_eq!(data, Some(b"abc".to_vec()));
+    ///                          burrito.read_nonempty(4)
+    ///                      });
+    /// assert_eq!(burrito.to_data().unwrap(), None);
+    /// # }
+    /// ```
+    pub fn read_nonempty(self, n: usize) -> Burrito<Option<Vec<u8>>, T> {
+        Burrito(self.0.read_nonempty(n))
+    }
+    /// Performs a read of up to _n_ bytes, like `read()`, and writes a hex dump of the bytes read
+    /// to `out`. The dump has the same format as `hexdump -C`: 16 bytes per row, each row
+    /// showing the offset, the bytes in hex and the bytes as ASCII. The bytes are also kept in
+    /// the `Burrito`, so they can still be used afterward.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io::Cursor;
+    /// use burrito::Burrito;
+    ///
+    /// let mut dump = Vec::new();
+    /// let data = Burrito::wrap(Ok(Cursor::new("Hello, world!\n"))).hexdump(64, &mut dump);
+    /// assert_eq!(data.to_data().unwrap(), b"Hello, world!\n");
+    /// assert_eq!(String::from_utf8(dump).unwrap(),
+    ///            "00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a        |Hello, world!.|\n\
+    ///             0000000e\n");
+    /// # }
+    /// ```
+    pub fn hexdump<W: Write>(self, n: usize, out: &mut W) -> Burrito<Vec<u8>, T> {
+        Burrito(self.0.hexdump(n, out))
+    }
+    /// Reads a length-prefixed frame: a 4 byte big-endian length, followed by that many bytes of
+    /// payload, which is stored in the `Burrito`. If EOF is reached before the whole frame has
+    /// been read, the `Burrito` fails with an error of the kind `io::ErrorKind::UnexpectedEof`.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let frame = Burrito::from_slice(b"\0\0\0\x05hello world").read_frame();
+    /// assert_eq!(frame.to_data().unwrap(), b"hello");
+    /// # }
+    /// ```
+    pub fn read_frame(self) -> Burrito<Vec<u8>, T> { Burrito(self.0.read_frame()) }
+    /// Reads to the end of the handle inside the burrito, returning a `Vec<u8>` of bytes.
+    pub fn read_to_end(self) -> Burrito<Vec<u8>, T> { Burrito(self.0.read_to_end()) }
+    /// Reads to the end of the handle, like `read_to_end()`, calling `on_progress` with the total
+    /// number of bytes read so far after each chunk. The handle is read in chunks of up to 8 KiB;
+    /// `on_progress` is called after every chunk, and once more with the final total when EOF is
+    /// reached, so it is always called at least once, even for an empty handle.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let mut reports = Vec::new();
+    /// let data = Burrito::from_slice(&[0; 10000]).read_to_end_progress(|n| reports.push(n));
+    /// assert_eq!(data.to_data().unwrap().len(), 10000);
+    /// assert_eq!(reports, [8192, 10000, 10000]);
+    /// # }
+    /// ```
+    pub fn read_to_end_progress<F: FnMut(usize)>(self, on_progress: F) -> Burrito<Vec<u8>, T> {
+        Burrito(self.0.read_to_end_progress(on_progress))
+    }
+    /// Generates an iterator over the handle in chunks of `size` bytes. Every chunk is exactly
+    /// `size` bytes long except the final one, which may be shorter if the handle runs out in the
+    /// middle of a chunk; a zero-length final chunk is never yielded. This will be wrapped in a
+    /// result because the IO handle may have failed at some point in the past, and also fails
+    /// with an error of the kind `io::ErrorKind::InvalidInput` if `size` is zero.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let chunks = Burrito::from_slice(b"abcdefgh").chunks(3).unwrap();
+    /// let chunks: Vec<Vec<u8>> = chunks.map(Result::unwrap).collect();
+    /// assert_eq!(chunks, [&b"abc"[..], b"def", b"gh"]);
+    /// # }
+    /// ```
+    pub fn chunks(self, size: usize) -> io::Result<Chunks<T>> { self.0.chunks(size) }
+    /// Copies everything remaining in the handle to `dst`, reading up to `chunk` bytes at a time,
+    /// and stores the number of bytes copied. Unlike `pipe()`, the reader is kept, positioned at
+    /// EOF, so that a seekable reader can be rewound and read again. A larger chunk means fewer
+    /// calls to `read` and `write`, at the cost of a larger buffer, which is allocated once. A
+    /// `chunk` of zero fails with an error of the kind `io::ErrorKind::InvalidInput`.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io::SeekFrom;
+    /// use burrito::Burrito;
+    ///
+    /// let mut copy = Vec::new();
+    /// let burrito = Burrito::from_slice(b"hello").spool_to(&mut copy, 2)
+    ///                      .seek(SeekFrom::Start(0)).read_to_end();
+    /// assert_eq!(burrito.to_data().unwrap(), copy);
+    /// # }
+    /// ```
+    pub fn spool_to<W: Write>(self, dst: &mut W, chunk: usize) -> Burrito<u64, T> {
+        Burrito(self.0.spool_to(dst, chunk))
+    }
+    /// Reads to the end of the handle, counting the occurrences of `byte`. The handle is read
+    /// through a single fixed buffer, so nothing is allocated however long it is, which makes
+    /// this an efficient way to count the lines of a large file.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let lines = Burrito::from_slice(b"one\ntwo\nthree\n").count_byte(b'\n');
+    /// assert_eq!(lines.to_data().unwrap(), 3);
+    /// # }
+    /// ```
+    pub fn count_byte(self, byte: u8) -> Burrito<usize, T> { Burrito(self.0.count_byte(byte)) }
+    /// Reads to the end of the handle, like `read_to_end()`, but reads no more than `max` bytes.
+    /// If the handle has more than `max` bytes, the `Burrito` fails with an error of the kind
+    /// `io::ErrorKind::InvalidData`, and the bytes read are discarded.
+    ///
+    /// The handle may be read in several chunks, but the limit applies to their total, and no
+    /// more than `max + 1` bytes will ever be read from the handle: the extra byte is how a
+    /// handle of more than `max` bytes is detected, so that one of exactly `max` bytes succeeds.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// assert!(Burrito::from_slice(b"12345").read_to_end_limited(5).is_good());
+    /// assert!(Burrito::from_slice(b"123456").read_to_end_limited(5).is_bad());
+    /// # }
+    /// ```
+    pub fn read_to_end_limited(self, max: usize) -> Burrito<Vec<u8>, T> {
+        Burrito(self.0.read_to_end_limited(max))
+    }
+    /// Reads everything from the handle into a `String`.
+    pub fn read_to_string(self) -> Burrito<String, T> { Burrito(self.0.read_to_string()) }
+    /// Reads everything from the handle into a `String`, replacing any invalid UTF-8 sequences
+    /// with U+FFFD REPLACEMENT CHARACTER. Unlike `read_to_string`, this only fails if reading
+    /// from the handle fails.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io::Cursor;
+    /// use burrito::Burrito;
+    ///
+    /// let text = Burrito::wrap(Ok(Cursor::new(b"abc\xffdef"))).read_to_string_lossy();
+    /// assert_eq!(text.to_data().unwrap(), "abc\u{FFFD}def");
+    /// # }
+    /// ```
+    pub fn read_to_string_lossy(self) -> Burrito<String, T> {
+        Burrito(self.0.read_to_string_lossy())
+    }
+}
 
 impl<A, T> Burrito<A, T> where T: Write {
-    /// Writes the content of the buf to the IO handle; returns the number of bytes written.
-    pub fn write(self, buf: &[u8]) -> Burrito<usize, T> { Burrito(self.0.write(buf)) }
+    /// Writes the content of the buf to the IO handle; returns the number of bytes written. The
+    /// buf can be anything which can be viewed as bytes, such as a `&[u8]`, a `Vec<u8>` or a
+    /// `&str`; the same is true of the other methods which write a buf.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let output = Burrito::wrap(Ok(Vec::new())).write_all("text, ").write_all(vec![b'o', b'r'])
+    ///                      .write_all(b" bytes").to_handle().unwrap();
+    /// assert_eq!(output, b"text, or bytes");
+    /// # }
+    /// ```
+    pub fn write<B: AsRef<[u8]>>(self, buf: B) -> Burrito<usize, T> {
+        Burrito(self.0.write(buf.as_ref()))
+    }
     /// Writes the content of the buf to the IO handle; will write all of the bytes unless it
     /// fails.
-    pub fn write_all(self, buf: &[u8]) -> Burrito<(), T> { Burrito(self.0.write_all(buf)) }
+    pub fn write_all<B: AsRef<[u8]>>(self, buf: B) -> Burrito<(), T> {
+        Burrito(self.0.write_all(buf.as_ref()))
+    }
+    /// Writes the content of the buf to the IO handle with a single call to `write`, failing with
+    /// an error of the kind `io::ErrorKind::WriteZero` if not all of it was written. Unlike
+    /// `write_all()`, which calls `write` until everything is written, this is for writers for
+    /// which a short write indicates a problem, such as datagram sockets.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io::Cursor;
+    /// use burrito::Burrito;
+    ///
+    /// let mut buf = [0; 4];
+    /// assert!(Burrito::wrap(Ok(Cursor::new(&mut buf[..]))).write_exact(b"12345").is_bad());
+    /// # }
+    /// ```
+    pub fn write_exact<B: AsRef<[u8]>>(self, buf: B) -> Burrito<(), T> {
+        Burrito(self.0.write_exact(buf.as_ref()))
+    }
+    /// Writes each chunk yielded by the iterator to the IO handle with `write_all()`, failing on
+    /// the first chunk which cannot be written. This avoids concatenating a lazily generated
+    /// payload into one buffer before writing it.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let chunks = (1..4).map(|n| vec![b'0' + n; n as usize]);
+    /// let out = Burrito::wrap(Ok(Vec::new())).write_chunks(chunks).to_handle();
+    /// assert_eq!(out.unwrap(), b"122333");
+    /// # }
+    /// ```
+    pub fn write_chunks<I, C>(self, chunks: I) -> Burrito<(), T>
+            where I: IntoIterator<Item = C>, C: AsRef<[u8]> {
+        Burrito(self.0.write_chunks(chunks))
+    }
+    /// Writes each item yielded by the iterator to the IO handle, with `sep` written between
+    /// each pair of items but not after the last one, failing on the first write which fails. An
+    /// empty iterator writes nothing at all, not even a separator.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let out = Burrito::wrap(Ok(Vec::new())).write_joined(&["a", "b", "c"], ", ").to_handle();
+    /// assert_eq!(out.unwrap(), b"a, b, c");
+    ///
+    /// let items: Vec<String> = Vec::new();
+    /// let out = Burrito::wrap(Ok(Vec::new())).write_joined(items, "\n").to_handle();
+    /// assert!(out.unwrap().is_empty());
+    /// # }
+    /// ```
+    pub fn write_joined<I, S, D>(self, items: I, sep: D) -> Burrito<(), T>
+            where I: IntoIterator<Item = S>, S: AsRef<[u8]>, D: AsRef<[u8]> {
+        Burrito(self.0.write_joined(items, sep))
+    }
+    /// Copies everything in `src` to the IO handle, and stores the number of bytes copied. The
+    /// handle is kept, so more can be written after it. If reading from `src` or writing to the
+    /// handle fails, the `Burrito` fails with that error.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io::Cursor;
+    /// use burrito::Burrito;
+    ///
+    /// let output = Burrito::wrap(Ok(Vec::new())).write_all("header\n")
+    ///                      .append_reader(Cursor::new("body\n"))
+    ///                      .write_all("footer\n");
+    /// assert_eq!(output.to_handle().unwrap(), b"header\nbody\nfooter\n");
+    /// # }
+    /// ```
+    pub fn append_reader<R: Read>(self, src: R) -> Burrito<u64, T> {
+        Burrito(self.0.append_reader(src))
+    }
+    /// Writes the content of the buf to the IO handle, like `write_all()`, but retries a write
+    /// of the remaining bytes if it is interrupted or would block, up to `retries` times in
+    /// total. After a write which would block, it sleeps for 10 milliseconds before retrying.
+    /// The `Burrito` fails on any other error, or once the retries are exhausted.
+    ///
+    /// ```no_run
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::net::TcpStream;
+    /// use burrito::Burrito;
+    ///
+    /// let stream = TcpStream::connect("localhost:12345");
+    /// let stream = stream.and_then(|s| s.set_nonblocking(true).map(|_| s));
+    /// let burrito = Burrito::wrap(stream).write_all_retry(&[0; 1 << 20], 100);
+    /// # }
+    /// ```
+    pub fn write_all_retry<B: AsRef<[u8]>>(self, buf: B, retries: usize) -> Burrito<(), T> {
+        Burrito(self.0.write_all_retry(buf.as_ref(), retries))
+    }
+    /// Writes the string to the IO handle followed by `\r\n`, the line terminator used by text
+    /// network protocols like SMTP, FTP and IRC.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let output = Burrito::wrap(Ok(Vec::new())).write_crlf_line("HELO example.com");
+    /// assert_eq!(output.to_handle().unwrap(), b"HELO example.com\r\n");
+    /// # }
+    /// ```
+    pub fn write_crlf_line(self, s: &str) -> Burrito<(), T> { Burrito(self.0.write_crlf_line(s)) }
+    /// Writes the string to the IO handle; returns the number of bytes written. Note that this is
+    /// a count of bytes, not of chars, and may end in the middle of a multi-byte char.
+    pub fn write_str(self, s: &str) -> Burrito<usize, T> { Burrito(self.0.write_str(s)) }
+    /// Writes the string to the IO handle; will write all of the string unless it fails.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let output = Burrito::wrap(Ok(Vec::new())).write_str_all("¡hola!").to_handle().unwrap();
+    /// assert_eq!(output, "¡hola!".as_bytes());
+    /// # }
+    /// ```
+    pub fn write_str_all(self, s: &str) -> Burrito<(), T> { Burrito(self.0.write_str_all(s)) }
     /// Writes formatted text to the IO handle.
     pub fn write_fmt(self, buf: fmt::Arguments) -> Burrito<(), T> {
         Burrito(self.0.write_fmt(buf))
     }
+    /// Writes formatted text to the IO handle, like `write_fmt()`, and stores the number of bytes
+    /// written. To count them, the text is formatted into a temporary `String` first and then
+    /// written all at once, whereas `write_fmt()` writes each piece as it is formatted, without
+    /// allocating.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let n = Burrito::wrap(Ok(Vec::new())).write_fmt_counted(format_args!("{} + {}", 2, 2));
+    /// assert_eq!(n.to_data().unwrap(), 5);
+    /// # }
+    /// ```
+    pub fn write_fmt_counted(self, buf: fmt::Arguments) -> Burrito<usize, T> {
+        Burrito(self.0.write_fmt_counted(buf))
+    }
+    /// Flushes any output buffered by the IO handle.
+    pub fn flush(self) -> Burrito<(), T> { Burrito(self.0.flush()) }
+    /// Flushes the IO handle and then drops it, consuming the `Burrito`. This returns the error
+    /// if flushing fails, or if the `Burrito` had already failed, so unlike simply dropping the
+    /// `Burrito` it gives a point at which lost output can be detected.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io::BufWriter;
+    /// use burrito::Burrito;
+    ///
+    /// let result = Burrito::wrap(Ok(BufWriter::new(Vec::new()))).write_all(b"done").close();
+    /// assert!(result.is_ok());
+    /// # }
+    /// ```
+    pub fn close(self) -> io::Result<()> { self.flush().to_data() }
+    /// Like `with`, runs `f` with a `Burrito` wrapping the IO handle and returns the value it
+    /// computed, but flushes the handle before dropping it, returning the error if flushing
+    /// fails. This makes sure everything `f` wrote has been written by the time it returns.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io::BufWriter;
+    /// use burrito::Burrito;
+    ///
+    /// let mut out = Vec::new();
+    /// let written = Burrito::wrap(Ok(BufWriter::new(&mut out))).with_writer(|w| {
+    ///     let w = w.write_str_all("hello\n");
+    ///     (6, w)
+    /// });
+    /// assert_eq!(written.unwrap(), 6);
+    /// assert_eq!(out, b"hello\n");
+    /// # }
+    /// ```
+    pub fn with_writer<R, B, F>(self, f: F) -> io::Result<R>
+            where F: FnOnce(Burrito<(), T>) -> (R, Burrito<B, T>) {
+        self.with(|burrito| {
+            let (result, burrito) = f(burrito);
+            (result, burrito.flush())
+        })
+    }
+    /// Writes a value to the IO handle using its `Display` implementation, without allocating an
+    /// intermediate `String`.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let output = Burrito::wrap(Ok(Vec::new())).write_display(&42).to_handle().unwrap();
+    /// assert_eq!(output, b"42");
+    /// # }
+    /// ```
+    pub fn write_display<D: fmt::Display + ?Sized>(self, value: &D) -> Burrito<(), T> {
+        Burrito(self.0.write_display(value))
+    }
+    /// Writes a value to the IO handle using its `Display` implementation, followed by a newline.
+    pub fn write_display_line<D: fmt::Display + ?Sized>(self, value: &D) -> Burrito<(), T> {
+        Burrito(self.0.write_display_line(value))
+    }
+    /// Wraps the IO handle in a `FlushOnDrop` writer, which flushes it when it is dropped. Errors
+    /// flushing the handle when it is dropped can't be reported; use `close()` to flush the
+    /// handle and check for errors.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::fs::File;
+    /// use std::io::BufWriter;
+    /// use burrito::Burrito;
+    ///
+    /// Burrito::wrap(File::create("/foo/bar/baz").map(BufWriter::new))
+    ///         .flush_on_drop()
+    ///         .write_all(b"this will be flushed");
+    /// # }
+    /// ```
+    pub fn flush_on_drop(self) -> Burrito<A, FlushOnDrop<T>> { Burrito(self.0.flush_on_drop()) }
+    /// Wraps the IO handle in a `PrefixWriter`, so that every line written to it afterward begins
+    /// with `prefix`.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let output = Burrito::wrap(Ok(Vec::new())).prefix(String::from("[worker] "))
+    ///                      .write_all(b"starting\n").write_all(b"done\n")
+    ///                      .to_handle().unwrap().into_inner();
+    /// assert_eq!(output, b"[worker] starting\n[worker] done\n");
+    /// # }
+    /// ```
+    pub fn prefix(self, prefix: String) -> Burrito<A, PrefixWriter<T>> {
+        Burrito(self.0.prefix(prefix))
+    }
+    /// Wraps the IO handle in a `LineEndingWriter`, so that the line endings of everything written
+    /// to it afterward are converted to `ending`. When converting to `LineEnding::Lf`, a `\r`
+    /// at the end of the last write is only written once the writer is unwrapped with its
+    /// `into_inner()` method; see `LineEndingWriter` for details.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::{Burrito, LineEnding};
+    ///
+    /// let output = Burrito::wrap(Ok(Vec::new())).convert_line_endings(LineEnding::CrLf)
+    ///                      .write_all(b"one\ntwo\r").write_all(b"\nthree\n")
+    ///                      .to_handle().unwrap().into_inner().unwrap();
+    /// assert_eq!(output, b"one\r\ntwo\r\nthree\r\n");
+    /// # }
+    /// ```
+    pub fn convert_line_endings(self, ending: LineEnding) -> Burrito<A, LineEndingWriter<T>> {
+        Burrito(self.0.convert_line_endings(ending))
+    }
+    /// Serializes `value` as JSON and writes it to the IO handle. No newline is written after it.
+    /// If the value can't be serialized, the `Burrito` fails with an error of the kind
+    /// `io::ErrorKind::InvalidData`. Requires the `json` feature.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let output = Burrito::wrap(Ok(Vec::new())).write_json(&vec!["a", "b"]).write_all(b"\n");
+    /// assert_eq!(output.to_handle().unwrap(), b"[\"a\",\"b\"]\n");
+    /// # }
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn write_json<V>(self, value: &V) -> Burrito<(), T> where V: serde::Serialize {
+        Burrito(self.0.write_json(value))
+    }
+    /// Wraps the IO handle in a `Base64Writer`, so that everything written to it afterward is
+    /// base64 encoded. The encoder must be finished with `base64_finish()` to write out the last
+    /// few bytes. Requires the `base64` feature.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let output = Burrito::wrap(Ok(Vec::new())).base64_encode()
+    ///                      .write_all(b"burrito").base64_finish()
+    ///                      .to_handle().unwrap();
+    /// assert_eq!(output, b"YnVycml0bw==");
+    /// # }
+    /// ```
+    #[cfg(feature = "base64")]
+    pub fn base64_encode(self) -> Burrito<A, Base64Writer<T>> { Burrito(self.0.base64_encode()) }
 }
 
 impl<A, T> Burrito<A, T> where T: Seek {
     /// Seeks to a position in the IO handle; returns the actual position that has been `seek`ed
     /// to.
     pub fn seek(self, pos: io::SeekFrom) -> Burrito<u64, T> { Burrito(self.0.seek(pos)) }
+    /// Finds the length of the IO handle by seeking to its end, then seeks back to where it was,
+    /// so that the position is unchanged. This is the same as the `Seek::stream_len()` method,
+    /// which is not yet stable.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io::{Cursor, SeekFrom};
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::wrap(Ok(Cursor::new("hello"))).seek(SeekFrom::Start(2)).stream_len();
+    /// assert_eq!(burrito.handle_ref().unwrap().position(), 2);
+    /// assert_eq!(burrito.to_data().unwrap(), 5);
+    /// # }
+    /// ```
+    pub fn stream_len(self) -> Burrito<u64, T> { Burrito(self.0.stream_len()) }
+}
+
+impl<A, T> Burrito<A, T> where T: Seek + Read {
+    /// Seeks to a position in the IO handle, then performs a read of up to _n_ bytes from there,
+    /// like `read()`.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io::{Cursor, SeekFrom};
+    /// use burrito::Burrito;
+    ///
+    /// let data = Burrito::wrap(Ok(Cursor::new("header:body"))).seek_read(SeekFrom::Start(7), 4);
+    /// assert_eq!(data.to_data().unwrap(), b"body");
+    /// # }
+    /// ```
+    pub fn seek_read(self, pos: io::SeekFrom, n: usize) -> Burrito<Vec<u8>, T> {
+        Burrito(self.0.seek_read(pos, n))
+    }
+    /// Rewinds the IO handle to its start, then passes a `Burrito` wrapping it to `f`, to make
+    /// another pass over the handle. The position of the handle and the most recently returned
+    /// data are discarded.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::from_slice(b"1\n2\n3\n").read_all_lines()
+    ///                      .reread(|burrito| burrito.read_to_end());
+    /// assert_eq!(burrito.to_data().unwrap(), b"1\n2\n3\n");
+    /// # }
+    /// ```
+    pub fn reread<B, F>(self, f: F) -> Burrito<B, T>
+            where F: FnOnce(Burrito<(), T>) -> Burrito<B, T> {
+        Burrito(self.0.seek(io::SeekFrom::Start(0))).and_then(|_, burrito| f(burrito))
+    }
+}
+
+impl<A, T> Burrito<A, T> where T: Seek + Write {
+    /// Seeks to a position in the IO handle, then writes all of the buf there, like
+    /// `write_all()`. The position of the handle is left at the end of the bytes written.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io::{Cursor, SeekFrom};
+    /// use burrito::Burrito;
+    ///
+    /// let output = Burrito::wrap(Ok(Cursor::new(Vec::new())))
+    ///                      .write_all(b"\0\0body").seek_write_all(SeekFrom::Start(0), b"04")
+    ///                      .to_handle().unwrap().into_inner();
+    /// assert_eq!(output, b"04body");
+    /// # }
+    /// ```
+    pub fn seek_write_all<B: AsRef<[u8]>>(self, pos: io::SeekFrom, buf: B) -> Burrito<(), T> {
+        Burrito(self.0.seek_write_all(pos, buf.as_ref()))
+    }
 }
 
 impl<A, T> Burrito<A, T> where T: BufRead {
     /// Fills the buffer on the buffered reader. Unlike the underlying fill_buf macro, this does
     /// not return a reference to the bytes in the buffer.
     pub fn fill_buf(self) -> Burrito<(), T> { Burrito(self.0.fill_buf()) }
+    /// Looks at the next byte in the buffered reader without consuming it, so that repeated
+    /// calls return the same byte until something is consumed. Holds `None` at EOF.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::from_slice(b"+1").peek_byte().and_then(|tag, burrito| {
+    ///     assert_eq!(tag, Some(b'+'));
+    ///     burrito.read_line()
+    /// });
+    /// assert_eq!(burrito.to_data().unwrap(), "+1");
+    /// # }
+    /// ```
+    pub fn peek_byte(self) -> Burrito<Option<u8>, T> { Burrito(self.0.peek_byte()) }
     /// Marks `amt` bytes in the buffer as consumed.
     pub fn consume(self, amt: usize) -> Burrito<(), T> { Burrito(self.0.consume(amt)) }
     /// Reads from the buffered reader until the `byte` is reached.
     pub fn read_until(self, byte: u8) -> Burrito<Vec<u8>, T> { Burrito(self.0.read_until(byte)) }
+    /// Reads from the buffered reader until the `byte` is reached, like `read_until()`, but does
+    /// not include the `byte` in the data, though it is still consumed. At EOF, the data is
+    /// whatever was read before it.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::from_slice(b"key=value").read_until_excl(b'=')
+    ///                      .and_then(|key, burrito| {
+    ///                          assert_eq!(key, b"key");
+    ///                          burrito.read_until_excl(b'=')
+    ///                      });
+    /// assert_eq!(burrito.to_data().unwrap(), b"value");
+    /// # }
+    /// ```
+    pub fn read_until_excl(self, byte: u8) -> Burrito<Vec<u8>, T> {
+        Burrito(self.0.read_until_excl(byte))
+    }
+    /// Reads from the buffered reader until the whole multi-byte sequence `delim` is reached,
+    /// even if it is split across refills of the reader's buffer. The delimiter is included in
+    /// the returned bytes. If EOF is reached first, all of the bytes read are returned. An empty
+    /// `delim` reads nothing.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let request = b"GET / HTTP/1.1\r\nHost: example.com\r\n\r\nbody";
+    /// let headers = Burrito::from_slice(request).read_until_bytes(b"\r\n\r\n");
+    /// assert_eq!(headers.to_data().unwrap(), &request[..37]);
+    /// # }
+    /// ```
+    pub fn read_until_bytes(self, delim: &[u8]) -> Burrito<Vec<u8>, T> {
+        Burrito(self.0.read_until_bytes(delim))
+    }
     /// Reads a line from the buffered reader.
     pub fn read_line(self) -> Burrito<String, T> { Burrito(self.0.read_line()) }
+    /// Reads a line from the buffered reader, like `read_line()`, but distinguishes EOF: the
+    /// `Burrito` holds `None` if no bytes could be read, and otherwise `Some` of the line,
+    /// including its line ending. A blank line is read as `Some("\n")`, never as `None`.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::from_slice(b"\n").read_line_opt().and_then(|line, burrito| {
+    ///     assert_eq!(line, Some(String::from("\n")));
+    ///     burrito.read_line_opt()
+    /// });
+    /// assert_eq!(burrito.to_data().unwrap(), None);
+    /// # }
+    /// ```
+    pub fn read_line_opt(self) -> Burrito<Option<String>, T> { Burrito(self.0.read_line_opt()) }
+    /// Reads lines from the buffered reader until a blank line or EOF, and stores them together,
+    /// with their line endings, as one string. The blank line, which may be `\n` or `\r\n`, is
+    /// consumed but not included. A line containing only whitespace is not blank. At EOF, the
+    /// string is empty.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::from_slice(b"From: lee\nTo: boats\n\nHello!\n").read_paragraph()
+    ///                      .and_then(|headers, burrito| {
+    ///                          assert_eq!(headers, "From: lee\nTo: boats\n");
+    ///                          burrito.read_paragraph()
+    ///                      });
+    /// assert_eq!(burrito.to_data().unwrap(), "Hello!\n");
+    /// # }
+    /// ```
+    pub fn read_paragraph(self) -> Burrito<String, T> { Burrito(self.0.read_paragraph()) }
+    /// Reads a line from the buffered reader, like `read_line()`, but reads no more than `max`
+    /// bytes. The limit includes the newline, so a line of exactly `max` bytes including its
+    /// newline is read successfully, as is a final line of `max` bytes ending at EOF. If `max`
+    /// bytes are read without reaching a newline or EOF, the `Burrito` fails with an error of the
+    /// kind `io::ErrorKind::InvalidData`, and the bytes read are discarded.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::from_slice(b"short\nmuch too long\n").read_line_limited(6)
+    ///                      .and_then(|line, burrito| {
+    ///                          assert_eq!(line, "short\n");
+    ///                          burrito.read_line_limited(6)
+    ///                      });
+    /// assert!(burrito.is_bad());
+    /// # }
+    /// ```
+    pub fn read_line_limited(self, max: usize) -> Burrito<String, T> {
+        Burrito(self.0.read_line_limited(max))
+    }
+    /// Reads a record ending with `terminator`, and stores it without the terminator, which is
+    /// consumed. A final record ending at EOF instead is read successfully. If more than `max`
+    /// bytes are read without reaching the terminator, the `Burrito` fails with an error of the
+    /// kind `io::ErrorKind::InvalidData`, and the bytes read are discarded; the limit does not
+    /// count the terminator, and no more than `max + 1` bytes are read. This makes it safe to
+    /// read records from an untrusted source.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::from_slice(b"ok\0too long\0").read_record(0, 4)
+    ///                      .and_then(|record, burrito| {
+    ///                          assert_eq!(record, b"ok");
+    ///                          burrito.read_record(0, 4)
+    ///                      });
+    /// assert_eq!(burrito.to_data().unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    ///
+    /// let record = Burrito::from_slice(b"four\0").read_record(0, 4);
+    /// assert_eq!(record.to_data().unwrap(), b"four");
+    /// # }
+    /// ```
+    pub fn read_record(self, terminator: u8, max: usize) -> Burrito<Vec<u8>, T> {
+        Burrito(self.0.read_record(terminator, max))
+    }
+    /// Reads a line terminated by `\r\n`, as used by text network protocols like SMTP, FTP and
+    /// IRC, and stores it without the terminator. A lone `\n` is also accepted as a terminator,
+    /// and is stripped in the same way, but a lone `\r` is not: it is kept as part of the line.
+    /// A final line ending at EOF is read without a terminator, and at EOF the line is empty. If
+    /// the line is not valid UTF-8, the `Burrito` fails with an error of the kind
+    /// `io::ErrorKind::InvalidData`.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::from_slice(b"220 ready\r\n250 ok\n").read_crlf_line()
+    ///                      .and_then(|line, burrito| {
+    ///                          assert_eq!(line, "220 ready");
+    ///                          burrito.read_crlf_line()
+    ///                      });
+    /// assert_eq!(burrito.to_data().unwrap(), "250 ok");
+    /// # }
+    /// ```
+    pub fn read_crlf_line(self) -> Burrito<String, T> { Burrito(self.0.read_crlf_line()) }
+    /// Reads a line from the buffered reader, appending it to `buf`, which is then stored in the
+    /// `Burrito`. The buffer is not cleared first, so lines can be accumulated; to reuse it for
+    /// each line, clear it between calls. At EOF, nothing is appended.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io::Cursor;
+    /// use burrito::Burrito;
+    ///
+    /// let lines = Burrito::wrap(Ok(Cursor::new("foo\nbar\n")))
+    ///                     .read_line_into(String::new())
+    ///                     .and_then(|buf, burrito| burrito.read_line_into(buf))
+    ///                     .to_data().unwrap();
+    /// assert_eq!(lines, "foo\nbar\n");
+    /// # }
+    /// ```
+    pub fn read_line_into(self, buf: String) -> Burrito<String, T> {
+        Burrito(self.0.read_line_into(buf))
+    }
+    /// Reads a line from the buffered reader and splits it at the first occurrence of `delim`,
+    /// storing the bytes before and after it as a pair. Neither half includes the delimiter or
+    /// the line ending. If the line does not contain `delim`, the `Burrito` fails with an error
+    /// of the kind `io::ErrorKind::InvalidData`.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io::Cursor;
+    /// use burrito::Burrito;
+    ///
+    /// let pair = Burrito::wrap(Ok(Cursor::new("key=value=1\n"))).read_pair(b'=');
+    /// assert_eq!(pair.to_data().unwrap(), (b"key".to_vec(), b"value=1".to_vec()));
+    /// # }
+    /// ```
+    pub fn read_pair(self, delim: u8) -> Burrito<(Vec<u8>, Vec<u8>), T> {
+        Burrito(self.0.read_pair(delim))
+    }
+    /// Reads a line from the buffered reader and splits it into fields separated by `sep`. The
+    /// line ending is not included in the last field. Quoting is not supported: every `sep` in
+    /// the line separates two fields. At EOF, the row is an empty `Vec`; a blank line is a row
+    /// with a single empty field. Fields which are not valid UTF-8 cause the `Burrito` to fail
+    /// with an error of the kind `io::ErrorKind::InvalidData`.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io::Cursor;
+    /// use burrito::Burrito;
+    ///
+    /// let row = Burrito::wrap(Ok(Cursor::new("1,2,,4\r\n"))).read_csv_row(b',');
+    /// assert_eq!(row.to_data().unwrap(), ["1", "2", "", "4"]);
+    /// # }
+    /// ```
+    pub fn read_csv_row(self, sep: u8) -> Burrito<Vec<String>, T> {
+        Burrito(self.0.read_csv_row(sep))
+    }
+    /// Reads every remaining line from the buffered reader, without their line endings, into a
+    /// `Vec`. Unlike `lines()`, this keeps the handle, and fails on the first error.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let lines = Burrito::from_slice(b"foo\nbar\r\nbaz").read_all_lines();
+    /// assert_eq!(lines.to_data().unwrap(), ["foo", "bar", "baz"]);
+    /// # }
+    /// ```
+    pub fn read_all_lines(self) -> Burrito<Vec<String>, T> { Burrito(self.0.read_all_lines()) }
+    /// Reads every remaining line from the buffered reader, like `read_all_lines()`, but keeps
+    /// only the first of each run of identical consecutive lines, like the `uniq` command. Apart
+    /// from the lines kept, only the most recent line is held to compare against, so this
+    /// takes no more memory for a run of a million duplicates than for one line.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let lines = Burrito::from_slice(b"a\na\nb\na\n").unique_lines();
+    /// assert_eq!(lines.to_data().unwrap(), ["a", "b", "a"]);
+    /// # }
+    /// ```
+    pub fn unique_lines(self) -> Burrito<Vec<String>, T> { Burrito(self.0.unique_lines()) }
+    /// Reads every remaining line from the buffered reader, like `read_all_lines()`, but keeps
+    /// only the first occurrence of each line, wherever its duplicates are. To find them, a
+    /// copy of every distinct line is kept in a `HashSet`, so the memory this takes grows with
+    /// the number of distinct lines, in addition to the lines kept.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::Burrito;
+    ///
+    /// let lines = Burrito::from_slice(b"a\na\nb\na\n").all_unique_lines();
+    /// assert_eq!(lines.to_data().unwrap(), ["a", "b"]);
+    /// # }
+    /// ```
+    pub fn all_unique_lines(self) -> Burrito<Vec<String>, T> {
+        Burrito(self.0.all_unique_lines())
+    }
+    /// Reads every remaining line from the buffered reader, without its line ending, and folds
+    /// them into a single value with `f`, starting from `init`. If reading a line fails, or `f`
+    /// returns an error, the `Burrito` fails with that error. A line is only passed to `f` once
+    /// it has been read successfully, so an IO error takes precedence over any error `f` would
+    /// have returned for that line.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io;
+    /// use burrito::Burrito;
+    ///
+    /// let sum = Burrito::from_slice(b"1\n2\n3\n").try_fold_lines(0, |sum, line| {
+    ///     line.parse::<i32>().map(|n| sum + n)
+    ///         .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    /// });
+    /// assert_eq!(sum.to_data().unwrap(), 6);
+    /// # }
+    /// ```
+    pub fn try_fold_lines<B, F>(self, init: B, f: F) -> Burrito<B, T>
+            where F: FnMut(B, String) -> io::Result<B> {
+        Burrito(self.0.try_fold_lines(init, f))
+    }
     /// Generates a Split Iterator of the underlying buffered reader. This will be wrapped in a
     /// result because the IO handle may have failed at some point in the past.
     pub fn split(self, byte: u8) -> io::Result<io::Split<T>> { self.0.split(byte) }
     /// Generates a Lines Iterator of the underlying buffered reader. This will be wrapped in a
     /// result because the IO handle may have failed at some point in the past.
     pub fn lines(self) -> io::Result<io::Lines<T>> { self.0.lines() }
+    /// Generates an iterator over the whitespace separated words of the underlying buffered
+    /// reader. This will be wrapped in a result because the IO handle may have failed at some
+    /// point in the past.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io::Cursor;
+    /// use burrito::Burrito;
+    ///
+    /// let words = Burrito::wrap(Ok(Cursor::new("1 2\n 3\t4\n"))).words().unwrap();
+    /// let words: Vec<String> = words.map(Result::unwrap).collect();
+    /// assert_eq!(words, ["1", "2", "3", "4"]);
+    /// # }
+    /// ```
+    pub fn words(self) -> io::Result<Words<T>> { self.0.words() }
+    /// Creates a `LineBurritos`, which reads the lines of the buffered reader one at a time and
+    /// wraps each in a `Burrito` borrowing the handle, so that every line can be handled with
+    /// IO on the same handle, and then returns the handle with `finish()`. If this `Burrito` has
+    /// failed, there are no lines, and `finish()` returns its error.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io::Cursor;
+    /// use burrito::Burrito;
+    ///
+    /// let mut lines = Burrito::from_slice(b"first\nsecond\n").line_burritos();
+    /// let mut lengths = Vec::new();
+    /// while let Some(line) = lines.next_line() {
+    ///     let (line, _) = line.ok().unwrap();
+    ///     lengths.push(line.len());
+    /// }
+    /// let cursor: Cursor<&[u8]> = lines.finish().to_handle().unwrap();
+    /// assert_eq!((lengths, cursor.position()), (vec![5, 6], 13));
+    /// # }
+    /// ```
+    pub fn line_burritos(self) -> LineBurritos<T> { iter::line_burritos(self.ignore()) }
+}
+
+/// These methods are implemented only for `Burrito`s wrapping a `Base64Writer`.
+#[cfg(feature = "base64")]
+impl<A, T> Burrito<A, Base64Writer<T>> where T: Write {
+
+    /// Encodes the final partial group of bytes written to the `Base64Writer`, along with any
+    /// padding, then unwraps the underlying handle.
+    pub fn base64_finish(self) -> Burrito<(), T> { Burrito(self.0.base64_finish()) }
+
+}
+
+/// These methods are implemented only for `Burrito`s wrapping a `Cursor` over a byte slice, like
+/// those constructed by `from_slice()`.
+impl<'a, A> Burrito<A, io::Cursor<&'a [u8]>> {
+
+    /// Reads the rest of the slice as a string, borrowing it from the slice rather than copying
+    /// it if it is valid UTF-8. Otherwise, like `read_to_string_lossy()`, it is copied into a new
+    /// `String` in which invalid sequences are replaced with U+FFFD REPLACEMENT CHARACTER.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::borrow::Cow;
+    /// use burrito::Burrito;
+    ///
+    /// let text = Burrito::from_slice(b"hello").read_to_cow().to_data().unwrap();
+    /// assert!(matches!(text, Cow::Borrowed("hello")));
+    /// let text = Burrito::from_slice(b"abc\xff").read_to_cow().to_data().unwrap();
+    /// assert!(matches!(text, Cow::Owned(_)));
+    /// # }
+    /// ```
+    pub fn read_to_cow(self) -> Burrito<Cow<'a, str>, io::Cursor<&'a [u8]>> {
+        Burrito(self.0.read_to_cow())
+    }
+
+}
+
+/// These methods are implemented only for `Burrito`s wrapping a `File`.
+impl<A> Burrito<A, fs::File> {
+
+    /// Sets the permissions of the file. On unix, permissions with a particular mode can be
+    /// constructed with `PermissionsExt::from_mode`.
+    ///
+    /// ```no_run
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// # #[cfg(unix)] {
+    /// use std::fs::{File, Permissions};
+    /// use std::os::unix::fs::PermissionsExt;
+    /// use burrito::Burrito;
+    ///
+    /// let burrito = Burrito::wrap(File::create("secret.key"))
+    ///                      .set_permissions(Permissions::from_mode(0o600))
+    ///                      .write_all(b"hunter2");
+    /// # }
+    /// # }
+    /// ```
+    pub fn set_permissions(self, perm: fs::Permissions) -> Burrito<(), fs::File> {
+        Burrito(self.0.set_permissions(perm))
+    }
+
+}
+
+/// These methods are implemented only for `Burrito`s wrapping a `TcpStream`.
+impl<A> Burrito<A, net::TcpStream> {
+
+    /// Reads a line from the stream, giving up if no complete line arrives within `dur`. The
+    /// limit applies to the line as a whole, however slowly its bytes arrive. If none of the line
+    /// has arrived by then, the `Burrito` is still good, but holds `None` instead of the line, and
+    /// nothing has been taken from the stream. If part of the line has arrived, that part can't
+    /// be put back, so the `Burrito` fails with an error of the kind `io::ErrorKind::TimedOut`.
+    /// A zero `dur` fails with an error of the kind `io::ErrorKind::InvalidInput`.
+    ///
+    /// Because `TcpStream` is not buffered, the stream is peeked at before anything is read, so
+    /// that nothing past the newline is consumed. The stream's read timeout and blocking mode
+    /// are not changed, and a nonblocking stream waits for the line like a blocking one. There is
+    /// no equivalent for the stdio `Burrito`, because a blocking read of stdin cannot be cancelled
+    /// without platform-specific handling.
+    pub fn read_line_timeout(self, dur: Duration) -> Burrito<Option<String>, net::TcpStream> {
+        Burrito(self.0.read_line_timeout(dur))
+    }
+
+    /// Splits the stream into a reading half and a writing half, which can be wrapped in
+    /// separate `Burrito`s and used from separate threads. This will be wrapped in a result
+    /// because the IO handle may have failed at some point in the past, or cloning it may fail.
+    ///
+    /// Both halves share the same underlying socket, so shutting down either half affects the
+    /// other.
+    ///
+    /// ```no_run
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io::BufReader;
+    /// use std::net::TcpStream;
+    /// use std::thread;
+    /// use burrito::{Burrito, FromAddr};
+    ///
+    /// let burrito = Burrito::from_addr::<_, TcpStream>("localhost:12345");
+    /// let (reader, writer) = burrito.split_halves().unwrap();
+    /// thread::spawn(move || Burrito::wrap(Ok(writer)).write_all(b"ping\n"));
+    /// let reply = Burrito::wrap(Ok(BufReader::new(reader))).read_line();
+    /// # }
+    /// ```
+    pub fn split_halves(self) -> io::Result<(ReadHalf, WriteHalf)> { self.0.split_halves() }
+
+    /// Wraps the stream in a `DeadlineStream`, so that every read and write on it afterward fails
+    /// with an error of the kind `io::ErrorKind::TimedOut` if it doesn't finish before `at`.
+    /// Unlike a read or write timeout, which limits each call separately, the deadline limits
+    /// all of the calls together, however many there are.
+    ///
+    /// ```no_run
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::io::BufReader;
+    /// use std::net::TcpStream;
+    /// use std::time::{Duration, Instant};
+    /// use burrito::{Burrito, FromAddr};
+    ///
+    /// let deadline = Instant::now() + Duration::from_secs(5);
+    /// let response = Burrito::from_addr::<_, TcpStream>("localhost:12345")
+    ///                       .set_deadline(deadline)
+    ///                       .write_all(b"GET\n")
+    ///                       .read_to_end();
+    /// # }
+    /// ```
+    pub fn set_deadline(self, at: Instant) -> Burrito<(), DeadlineStream> {
+        Burrito(self.0.set_deadline(at))
+    }
+
+    /// Checks whether there is data which can be read from the stream, without reading it. This
+    /// is `is_read_ready()` with a zero timeout, followed by a peek at the stream to tell data
+    /// apart from EOF. The `Burrito` holds `false` if the stream has reached EOF, and fails if the
+    /// stream has an error pending.
+    ///
+    /// On unix platforms the check uses `poll`, and doesn't wait at all. Elsewhere it peeks with
+    /// a read timeout, which can't be zero, so it may wait up to a millisecond for data.
+    ///
+    /// This doesn't peek with the socket switched to nonblocking mode for the moment, because the
+    /// standard library can't tell whether a socket was already nonblocking, and so such a peek
+    /// couldn't restore the mode it found; a nonblocking socket would be left blocking.
+    ///
+    /// ```no_run
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::net::TcpStream;
+    /// use burrito::{Burrito, FromAddr};
+    ///
+    /// let burrito = Burrito::from_addr::<_, TcpStream>("localhost:12345").has_data()
+    ///                      .and_then(|ready, burrito| burrito.read(if ready { 1024 } else { 0 }));
+    /// # }
+    /// ```
+    pub fn has_data(self) -> Burrito<bool, net::TcpStream> { Burrito(self.0.has_data()) }
+
+    /// Waits up to `timeout` for the stream to become readable, returning whether it is, without
+    /// reading anything. A stream which has reached EOF or has an error pending is readable, since
+    /// reading it won't block. This borrows the `Burrito` rather than consuming it, so it remains
+    /// usable afterward. If the `Burrito` has failed, this returns an error like its own.
+    ///
+    /// On unix platforms this uses `poll`; elsewhere it peeks at the stream with a read timeout.
+    ///
+    /// ```no_run
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::net::TcpStream;
+    /// use std::time::Duration;
+    /// use burrito::{Burrito, FromAddr};
+    ///
+    /// let burrito = Burrito::from_addr::<_, TcpStream>("localhost:12345");
+    /// if burrito.is_read_ready(Duration::from_millis(10)).unwrap() {
+    ///     let data = burrito.read(1024);
+    /// }
+    /// # }
+    /// ```
+    pub fn is_read_ready(&self, timeout: Duration) -> io::Result<bool> {
+        self.0.is_read_ready(timeout)
+    }
+
+    /// Sets the `TCP_NODELAY` option on the stream, disabling Nagle's algorithm if `on` is true.
+    ///
+    /// ```no_run
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::net::TcpStream;
+    /// use burrito::{Burrito, FromAddr};
+    ///
+    /// let burrito = Burrito::from_addr::<_, TcpStream>("localhost:12345")
+    ///                       .set_nodelay(true)
+    ///                       .set_ttl(64);
+    /// # }
+    /// ```
+    pub fn set_nodelay(self, on: bool) -> Burrito<(), net::TcpStream> {
+        Burrito(self.0.set_nodelay(on))
+    }
+
+    /// Sets the `IP_TTL` option on the stream, the time-to-live of every packet it sends.
+    pub fn set_ttl(self, ttl: u32) -> Burrito<(), net::TcpStream> { Burrito(self.0.set_ttl(ttl)) }
+
+    /// Sets the `SO_KEEPALIVE` option on the stream. The standard library does not expose this
+    /// option, so it is only available on unix platforms, and the keepalive interval is left at
+    /// the system default.
+    #[cfg(unix)]
+    pub fn set_keepalive(self, on: bool) -> Burrito<(), net::TcpStream> {
+        Burrito(self.0.set_keepalive(on))
+    }
+
+}
+
+/// These methods are implemented only for `Burrito`s wrapping a `UdpSocket`.
+impl<A> Burrito<A, net::UdpSocket> {
+
+    /// Sets the `SO_BROADCAST` option on the socket, allowing it to send to broadcast addresses.
+    ///
+    /// ```no_run
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use std::net::{Ipv4Addr, UdpSocket};
+    /// use burrito::Burrito;
+    ///
+    /// let group = Ipv4Addr::new(239, 255, 0, 1);
+    /// let burrito = Burrito::wrap(UdpSocket::bind("0.0.0.0:5353"))
+    ///                      .set_broadcast(true)
+    ///                      .join_multicast_v4(&group, &Ipv4Addr::new(0, 0, 0, 0));
+    /// # }
+    /// ```
+    pub fn set_broadcast(self, on: bool) -> Burrito<(), net::UdpSocket> {
+        Burrito(self.0.set_broadcast(on))
+    }
+
+    /// Joins the IPv4 multicast group `multiaddr` on the interface with the address `interface`.
+    /// An `interface` of `0.0.0.0` lets the system choose the interface.
+    pub fn join_multicast_v4(self, multiaddr: &net::Ipv4Addr, interface: &net::Ipv4Addr)
+            -> Burrito<(), net::UdpSocket> {
+        Burrito(self.0.join_multicast_v4(multiaddr, interface))
+    }
+
+    /// Leaves the IPv4 multicast group `multiaddr` on the interface with the address `interface`.
+    pub fn leave_multicast_v4(self, multiaddr: &net::Ipv4Addr, interface: &net::Ipv4Addr)
+            -> Burrito<(), net::UdpSocket> {
+        Burrito(self.0.leave_multicast_v4(multiaddr, interface))
+    }
+
+}
+
+/// These methods are implemented only for `Burrito`s wrapping a `ReconnectingStream`.
+impl<A> Burrito<A, ReconnectingStream> {
+
+    /// Drops the current connection and opens a new one to the same address. The old connection
+    /// is closed even if the new one cannot be opened.
+    ///
+    /// This also heals a `Burrito` which has failed, as long as the error came from the
+    /// `ReconnectingStream`, which includes its address in its errors: failing to connect, read
+    /// or write, including failing to reconnect. Any other error, including a fused one, is
+    /// returned unchanged.
+    ///
+    /// ```no_run
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::{Burrito, FromAddr, ReconnectingStream};
+    ///
+    /// let burrito = Burrito::from_addr::<_, ReconnectingStream>("localhost:12345");
+    /// let burrito = burrito.write_all(b"hello").reconnect().write_all(b"hello again");
+    /// # }
+    /// ```
+    ///
+    /// To reconnect only after a failure:
+    ///
+    /// ```no_run
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::{Burrito, FromAddr, ReconnectingStream};
+    ///
+    /// let burrito = Burrito::from_addr::<_, ReconnectingStream>("localhost:12345")
+    ///                      .write_all(b"hello");
+    /// let burrito = if burrito.is_bad() { burrito.reconnect() } else { burrito };
+    /// let burrito = burrito.write_all(b"hello again");
+    /// # }
+    /// ```
+    pub fn reconnect(self) -> Burrito<(), ReconnectingStream> { Burrito(self.0.reconnect()) }
+
 }
 
 /// These methods are implemented only for the stdio `Burrito`. Note that `RealWorld` implements
@@ -385,14 +2467,224 @@ impl<A> Burrito<A, RealWorld> {
         Burrito(self.0.read_line())
     }
 
+    /// Discards this `Burrito`, whether it has failed or not, and returns a fresh stdio `Burrito`.
+    /// Unlike `or()` and `or_else()`, which only replace a failed `Burrito`, this clears the
+    /// error unconditionally, which is useful in a REPL-style loop where one bad read should not
+    /// end the session. Any output buffered by the old `Burrito` is flushed as it is dropped, and
+    /// the byte counts of the new `Burrito` start from zero.
+    ///
+    /// ```no_run
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::burrito;
+    ///
+    /// let mut session = burrito();
+    /// loop {
+    ///     session = session.read_line().and_then(|line, b| b.print_line(line.trim()));
+    ///     if session.is_bad() {
+    ///         session = session.reset();
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn reset(self) -> Burrito<(), RealWorld> { Burrito::default() }
+
+    /// The number of bytes read from stdin by this `Burrito`. Each stdio `Burrito` created by
+    /// `burrito()` starts counting from zero. If the `Burrito` has failed, this is zero.
+    pub fn total_in(&self) -> u64 { self.0.total_in() }
+
+    /// The number of bytes written to stdout and stderr by this `Burrito`. Each stdio `Burrito`
+    /// created by `burrito()` starts counting from zero. If the `Burrito` has failed, this is
+    /// zero.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::burrito;
+    ///
+    /// let burrito = burrito().print_line("hello").write_all_to_err(b"world\n");
+    /// assert_eq!(burrito.total_out(), 12);
+    /// # }
+    /// ```
+    pub fn total_out(&self) -> u64 { self.0.total_out() }
+
+    /// Creates another stdio `Burrito`, which can be handed to a helper function that wants to
+    /// print without threading this `Burrito` through it. Both share the same OS streams and
+    /// the same locks, but each has its own output buffer, so output buffered by one is not
+    /// flushed by the other; with block buffering, their output may be interleaved in chunks
+    /// rather than lines. The new `Burrito` has the same buffering mode, and its byte counts
+    /// start from zero. If this `Burrito` has failed, the new one fails with an error of the
+    /// same kind and message.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::burrito;
+    ///
+    /// let main = burrito().set_line_buffered(true);
+    /// let helper = main.clone_world().print_line("from the helper");
+    /// main.print_line("from main");
+    /// # }
+    /// ```
+    pub fn clone_world(&self) -> Burrito<(), RealWorld> { Burrito(self.0.clone_world()) }
+
+    /// Chooses whether stdout is line buffered or block buffered. When line buffered, every
+    /// newline written causes stdout to be flushed; when block buffered, it is only flushed
+    /// once the buffer fills or `flush()` is called. As in C stdio, stdout is line buffered by
+    /// default if it is a terminal, and block buffered otherwise.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::burrito;
+    ///
+    /// burrito().set_line_buffered(true).print_line("Reading input...");
+    /// # }
+    /// ```
+    pub fn set_line_buffered(self, on: bool) -> Burrito<(), RealWorld> {
+        Burrito(self.0.set_line_buffered(on))
+    }
+
+    /// Locks stdin, returning a `Burrito` which reads from it through the standard library's
+    /// buffer, and holds the lock until it is dropped. Since stdin is not locked again for each
+    /// call, methods like `read_line()`, `read_until()` and `lines()` on the new `Burrito` are
+    /// much faster than reading through the stdio `Burrito` when reading a lot of input. Any
+    /// output buffered by this `Burrito` is flushed, and its handles to stdout and stderr are
+    /// dropped; to keep writing to stdout while reading, use `lock()` instead.
+    ///
+    /// ```no_run
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::burrito;
+    ///
+    /// let lines = burrito().buffered_stdin().read_all_lines().to_data().unwrap();
+    /// println!("{} lines", lines.len());
+    /// # }
+    /// ```
+    pub fn buffered_stdin(self) -> Burrito<(), BufStdin> { Burrito(self.0.buffered_stdin()) }
+
+    /// Locks stdin, stdout and stderr, returning a `Burrito` which holds all three locks until it
+    /// is dropped. Because stdio is not locked again for every call, this is much more efficient
+    /// than the stdio `Burrito` for reading or writing many small pieces, and since the locked
+    /// handle implements `BufRead`, methods like `lines()` and `read_until()` work on it. Any
+    /// output buffered by this `Burrito` is flushed before the locks are taken. The locked
+    /// `Burrito` does not buffer output itself, and does not count bytes read or written.
+    ///
+    /// While the locked `Burrito` exists, other threads block on any use of stdio, including the
+    /// standard library's `print!` macros. On the same thread, printing with `println!` still
+    /// works, but reading from stdin by any other means, such as with another stdio `Burrito`,
+    /// will deadlock. Use the locked `Burrito` in a scoped block to make clear when the locks are
+    /// released.
+    ///
+    /// ```no_run
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::burrito;
+    ///
+    /// {
+    ///     let locked = burrito().lock();
+    ///     for line in locked.lines().unwrap() {
+    ///         println!("{}", line.unwrap().to_uppercase());
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn lock(self) -> Burrito<(), LockedWorld> { Burrito(self.0.lock()) }
+
+    /// Locks stdout and passes a `Burrito` wrapping the locked handle to `f`, releasing the lock
+    /// when `f` returns. Everything `f` writes is written atomically with respect to anything
+    /// else which locks stdout, including other stdio `Burrito`s and the standard library's own
+    /// `print!` macros, which will block on other threads until the lock is released. Any output
+    /// buffered by this `Burrito` is flushed before the lock is taken, and the locked handle is
+    /// flushed before it is released.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::burrito;
+    ///
+    /// burrito().with_stdout_locked(|out| {
+    ///     out.write_all(b"these lines will ").write_all(b"not be interleaved\n")
+    /// });
+    /// # }
+    /// ```
+    pub fn with_stdout_locked<F>(self, f: F) -> Burrito<(), RealWorld>
+            where F: FnOnce(Burrito<(), LockedOut>) -> Burrito<(), LockedOut> {
+        Burrito(self.0.with_stdout_locked(|out| f(Burrito(out)).0))
+    }
+
+    /// Locks stdin once and returns an iterator over its lines, without their line endings. This
+    /// is much more efficient than calling `read_line()` repeatedly, since stdin is not locked
+    /// again for each line. Any buffered output is flushed first. If the `Burrito` has failed,
+    /// the iterator yields its error and then ends.
+    ///
+    /// ```no_run
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::burrito;
+    ///
+    /// for line in burrito().stdin_lines() {
+    ///     println!("{}", line.unwrap().to_uppercase());
+    /// }
+    /// # }
+    /// ```
+    pub fn stdin_lines(self) -> impl Iterator<Item = io::Result<String>> {
+        let (lines, err) = match self {
+            Burrito(Good(_, mut rw)) => match rw.flush() {
+                Ok(..) => (Some(rw.stdin.lock().lines()), None),
+                Err(err) => (None, Some(err)),
+            },
+            Burrito(Bad(err)) => (None, Some(err)),
+        };
+        err.map(Err).into_iter().chain(lines.into_iter().flatten())
+    }
+
+    /// Locks stdin once and calls `f` with each of its lines, without their line endings, until
+    /// EOF. The `Burrito` is good if EOF was reached cleanly, and fails if reading a line fails.
+    /// This is the loop at the heart of a filter program like `cat` or `grep`. Any buffered
+    /// output is flushed first.
+    ///
+    /// ```no_run
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::burrito;
+    ///
+    /// let mut count = 0;
+    /// burrito().for_each_stdin_line(|line| if line.contains("burrito") { count += 1 })
+    ///          .print_line(&format!("{} burritos", count));
+    /// # }
+    /// ```
+    pub fn for_each_stdin_line<F: FnMut(String)>(self, f: F) -> Burrito<(), RealWorld> {
+        Burrito(self.0.for_each_stdin_line(f))
+    }
+
     /// Performs a write to stderr instead of stdout.
-    pub fn write_to_err(self, buf: &[u8]) -> Burrito<usize, RealWorld> {
-        Burrito(self.0.write_to_err(buf))
+    pub fn write_to_err<B: AsRef<[u8]>>(self, buf: B) -> Burrito<usize, RealWorld> {
+        Burrito(self.0.write_to_err(buf.as_ref()))
     }
 
     /// Performs a write_all to stderr instead of stdout.
-    pub fn write_all_to_err(self, buf: &[u8]) -> Burrito<(), RealWorld> {
-        Burrito(self.0.write_all_to_err(buf))
+    pub fn write_all_to_err<B: AsRef<[u8]>>(self, buf: B) -> Burrito<(), RealWorld> {
+        Burrito(self.0.write_all_to_err(buf.as_ref()))
+    }
+
+    /// Prints a string to stderr, with no newline affixed to the end, and flushes stderr so that
+    /// it appears immediately. Together with `\r`, this can redraw a progress indicator in
+    /// place.
+    ///
+    /// ```
+    /// # extern crate burrito;
+    /// # fn main() {
+    /// use burrito::burrito;
+    ///
+    /// let burrito = (0..=100).step_by(25).fold(burrito(), |burrito, percent| {
+    ///     burrito.print_to_err(&format!("\rdownloading... {}%", percent))
+    /// });
+    /// burrito.print_to_err("\n");
+    /// # }
+    /// ```
+    pub fn print_to_err(self, buf: &str) -> Burrito<(), RealWorld> {
+        Burrito(self.0.print_to_err(buf))
     }
 
     /// Performs a write_fmt to stderr instead of stdout.
@@ -0,0 +1,53 @@
+use std::cmp;
+use std::io::{self, Read, BufRead};
+
+/// A reader over a sequence of in-memory chunks of bytes.
+///
+/// This is created by `Burrito::from_chunks()`. Reading yields the bytes of each chunk in turn;
+/// chunks are pulled from the underlying iterator only as they are needed.
+pub struct ChunkReader {
+    chunks: Box<dyn Iterator<Item = Vec<u8>>>,
+    current: Vec<u8>,
+    pos: usize,
+}
+
+impl ChunkReader {
+
+    /// Creates a reader over the chunks yielded by `chunks`.
+    pub fn new<I>(chunks: I) -> ChunkReader
+            where I: IntoIterator<Item = Vec<u8>>, I::IntoIter: 'static {
+        ChunkReader { chunks: Box::new(chunks.into_iter()), current: Vec::new(), pos: 0 }
+    }
+
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = {
+            let available = self.fill_buf()?;
+            let n = cmp::min(available.len(), buf.len());
+            buf[..n].copy_from_slice(&available[..n]);
+            n
+        };
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for ChunkReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        while self.pos == self.current.len() {
+            match self.chunks.next() {
+                Some(chunk) => {
+                    self.current = chunk;
+                    self.pos = 0;
+                }
+                None => break,
+            }
+        }
+        Ok(&self.current[self.pos..])
+    }
+    fn consume(&mut self, amt: usize) {
+        self.pos = cmp::min(self.pos + amt, self.current.len());
+    }
+}
@@ -1,30 +1,264 @@
-use std::io::{self, Read, Write};
+use std::fmt;
+use std::io::{self, Read, Write, BufRead, IsTerminal};
+
+const BUFFER_SIZE: usize = 8 * 1024;
 
 pub struct RealWorld {
     pub stdin: io::Stdin,
     pub stdout: io::Stdout,
     pub stderr: io::Stderr,
+    buffer: Vec<u8>,
+    line_buffered: bool,
+    bytes_in: u64,
+    bytes_out: u64,
+}
+
+/// A locked handle to stderr which counts the bytes written to it in the `RealWorld`.
+pub struct ErrWriter<'a> {
+    stderr: io::StderrLock<'static>,
+    bytes_out: &'a mut u64,
+}
+
+/// A handle to stdout which holds the lock on stdout for as long as it exists.
+///
+/// This is created by the `with_stdout_locked()` method on `Burrito`. It is not buffered beyond
+/// the buffering done by the standard library's stdout.
+pub struct LockedOut {
+    stdout: io::StdoutLock<'static>,
+    bytes_out: u64,
+}
+
+/// A handle which holds the locks on stdin, stdout and stderr for as long as it exists.
+///
+/// This is created by the `lock()` method on `Burrito`. Reading reads from stdin, writing writes
+/// to stdout, and stderr can be written to through the `stderr` field. It is not buffered beyond
+/// the buffering done by the standard library's stdio handles.
+pub struct LockedWorld {
+    pub stdin: io::StdinLock<'static>,
+    pub stdout: io::StdoutLock<'static>,
+    pub stderr: io::StderrLock<'static>,
+}
+
+/// A buffered handle to stdin which holds the lock on stdin for as long as it exists.
+///
+/// This is created by the `buffered_stdin()` method on `Burrito`. Reading from it uses the
+/// buffer the standard library keeps for stdin directly, without locking stdin for every call.
+pub struct BufStdin {
+    stdin: io::StdinLock<'static>,
+}
+
+/// The handle wrapped by the stderr-only `Burrito` returned by `burrito_err()`.
+pub struct StdErrWorld {
+    pub stderr: io::Stderr,
+}
+
+impl RealWorld {
+
+    /// Chooses between line buffering and block buffering for stdout, flushing anything already
+    /// buffered.
+    pub fn set_line_buffered(&mut self, on: bool) -> io::Result<()> {
+        self.line_buffered = on;
+        self.flush()
+    }
+
+    /// Reads a line from stdin, flushing stdout first as C stdio does.
+    pub fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        self.flush()?;
+        let n = self.stdin.read_line(buf)?;
+        self.bytes_in += n as u64;
+        Ok(n)
+    }
+
+    /// Flushes stdout, then locks stdin and calls `f` with each line read from it, without its
+    /// line ending, until EOF.
+    pub fn for_each_line<F: FnMut(String)>(&mut self, mut f: F) -> io::Result<()> {
+        self.flush()?;
+        let mut stdin = self.stdin.lock();
+        loop {
+            let mut line = String::new();
+            let n = stdin.read_line(&mut line)?;
+            if n == 0 { return Ok(()) }
+            self.bytes_in += n as u64;
+            if line.ends_with('\n') {
+                line.pop();
+                if line.ends_with('\r') { line.pop(); }
+            }
+            f(line);
+        }
+    }
+
+    /// Flushes buffered output and locks stdin for reading.
+    pub fn lock_stdin(&mut self) -> io::Result<BufStdin> {
+        self.flush()?;
+        Ok(BufStdin { stdin: self.stdin.lock() })
+    }
+
+    /// Locks stderr for writing.
+    pub fn err_writer<'a>(&'a mut self) -> ErrWriter<'a> {
+        ErrWriter { stderr: self.stderr.lock(), bytes_out: &mut self.bytes_out }
+    }
+
+    /// Flushes buffered output and locks stdout.
+    pub fn lock_stdout(&mut self) -> io::Result<LockedOut> {
+        self.flush()?;
+        Ok(LockedOut { stdout: self.stdout.lock(), bytes_out: 0 })
+    }
+
+    /// Flushes and releases a lock on stdout taken by `lock_stdout`.
+    pub fn unlock_stdout(&mut self, mut out: LockedOut) -> io::Result<()> {
+        self.bytes_out += out.bytes_out;
+        out.flush()
+    }
+
+    /// Flushes buffered output and locks stdin, stdout and stderr.
+    pub fn lock(&mut self) -> io::Result<LockedWorld> {
+        self.flush()?;
+        Ok(LockedWorld {
+            stdin: self.stdin.lock(),
+            stdout: self.stdout.lock(),
+            stderr: self.stderr.lock(),
+        })
+    }
+
+    /// Creates another `RealWorld` using the same stdio handles, with the same buffering mode.
+    /// It has its own empty output buffer, and counts bytes from zero.
+    pub fn clone_world(&self) -> RealWorld {
+        let mut world = RealWorld::default();
+        world.line_buffered = self.line_buffered;
+        world
+    }
+
+    /// The number of bytes read from stdin by this `RealWorld`.
+    pub fn total_in(&self) -> u64 { self.bytes_in }
+
+    /// The number of bytes written to stdout and stderr by this `RealWorld`, including output
+    /// which is still buffered.
+    pub fn total_out(&self) -> u64 { self.bytes_out }
+
 }
 
+/// Like C stdio, stdout is line buffered if it is a terminal and block buffered otherwise.
 impl Default for RealWorld {
     fn default() -> RealWorld {
+        let stdout = io::stdout();
+        let line_buffered = stdout.is_terminal();
         RealWorld {
             stdin: io::stdin(),
-            stdout: io::stdout(),
+            stdout,
             stderr: io::stderr(),
+            buffer: Vec::with_capacity(BUFFER_SIZE),
+            line_buffered,
+            bytes_in: 0,
+            bytes_out: 0,
         }
     }
 }
 
+impl Default for StdErrWorld {
+    fn default() -> StdErrWorld { StdErrWorld { stderr: io::stderr() } }
+}
+
 impl Read for RealWorld {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        self.stdin.lock().read(buf)
+        self.flush()?;
+        let n = self.stdin.lock().read(buf)?;
+        self.bytes_in += n as u64;
+        Ok(n)
     }
 }
 
 impl Write for RealWorld {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.stdout.lock().write(buf)
+        let flushes = self.line_buffered && buf.contains(&b'\n');
+        if !flushes && self.buffer.len() + buf.len() < BUFFER_SIZE {
+            self.buffer.extend_from_slice(buf);
+            self.bytes_out += buf.len() as u64;
+            return Ok(buf.len())
+        }
+        // Write what is buffered, then buf itself, straight to stdout, so that an error is
+        // returned by the call which hit it, and never after any of buf has been taken.
+        self.flush()?;
+        let n = self.stdout.lock().write(buf)?;
+        self.bytes_out += n as u64;
+        Ok(n)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() { return Ok(()) }
+        let mut stdout = self.stdout.lock();
+        while !self.buffer.is_empty() {
+            match stdout.write(&self.buffer) {
+                Ok(0) => {
+                    let msg = "failed to write buffered output";
+                    return Err(io::Error::new(io::ErrorKind::WriteZero, msg))
+                }
+                Ok(n) => { self.buffer.drain(..n); }
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        stdout.flush()
+    }
+}
+
+impl<'a> Write for ErrWriter<'a> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.stderr.write(buf)?;
+        *self.bytes_out += n as u64;
+        Ok(n)
+    }
+    fn flush(&mut self) -> io::Result<()> { self.stderr.flush() }
+}
+
+impl Write for LockedOut {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.stdout.write(buf)?;
+        self.bytes_out += n as u64;
+        Ok(n)
+    }
+    fn flush(&mut self) -> io::Result<()> { self.stdout.flush() }
+}
+
+impl Read for BufStdin {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> { self.stdin.read(buf) }
+}
+
+impl BufRead for BufStdin {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> { self.stdin.fill_buf() }
+    fn consume(&mut self, amt: usize) { self.stdin.consume(amt) }
+}
+
+/// As with `RealWorld`, stdout is flushed before reading from stdin.
+impl Read for LockedWorld {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.stdout.flush()?;
+        self.stdin.read(buf)
+    }
+}
+
+impl BufRead for LockedWorld {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.stdout.flush()?;
+        self.stdin.fill_buf()
+    }
+    fn consume(&mut self, amt: usize) { self.stdin.consume(amt) }
+}
+
+impl Write for LockedWorld {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.stdout.write(buf) }
+    fn flush(&mut self) -> io::Result<()> { self.stdout.flush() }
+}
+
+impl Write for StdErrWorld {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.stderr.lock().write(buf) }
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> { self.stderr.lock().write_all(buf) }
+    fn write_fmt(&mut self, fmt: fmt::Arguments) -> io::Result<()> {
+        self.stderr.lock().write_fmt(fmt)
+    }
+    fn flush(&mut self) -> io::Result<()> { self.stderr.lock().flush() }
+}
+
+impl Drop for RealWorld {
+    fn drop(&mut self) {
+        let _ = self.flush();
     }
-    fn flush(&mut self) -> io::Result<()> { Ok(()) }
 }
@@ -0,0 +1,90 @@
+use std::error;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{self, SocketAddr, ToSocketAddrs};
+
+use constructors::FromAddr;
+
+/// A `TcpStream` which remembers the address it is connected to, so that the connection can be
+/// re-established by the `reconnect()` method on `Burrito`.
+///
+/// The address is remembered by its errors too: every error from connecting, reading or writing
+/// has the same kind and message as the underlying error, but wraps it along with the address,
+/// so that a `Burrito` which has failed can still be reconnected. Because of the wrapping, these
+/// errors have no `raw_os_error()`.
+pub struct ReconnectingStream {
+    addr: SocketAddr,
+    stream: net::TcpStream,
+}
+
+impl ReconnectingStream {
+
+    /// Opens a connection to a single socket address.
+    pub fn connect(addr: SocketAddr) -> io::Result<ReconnectingStream> {
+        let stream = net::TcpStream::connect(addr).map_err(|err| with_addr(err, addr))?;
+        Ok(ReconnectingStream { addr, stream })
+    }
+
+    /// The address this stream connects to.
+    pub fn addr(&self) -> SocketAddr { self.addr }
+
+    /// A reference to the underlying `TcpStream`.
+    pub fn get_ref(&self) -> &net::TcpStream { &self.stream }
+
+}
+
+impl FromAddr for ReconnectingStream {
+    fn from_addr<A: ToSocketAddrs>(addr: A) -> io::Result<ReconnectingStream> {
+        let addrs: Vec<SocketAddr> = addr.to_socket_addrs()?.collect();
+        let stream = match net::TcpStream::connect(&addrs[..]) {
+            Ok(stream) => stream,
+            Err(err) => return Err(match addrs.first() {
+                Some(&addr) => with_addr(err, addr),
+                None => err,
+            }),
+        };
+        let addr = stream.peer_addr()?;
+        Ok(ReconnectingStream { addr, stream })
+    }
+}
+
+impl Read for ReconnectingStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.stream.read(buf).map_err(|err| with_addr(err, self.addr))
+    }
+}
+
+impl Write for ReconnectingStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.stream.write(buf).map_err(|err| with_addr(err, self.addr))
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush().map_err(|err| with_addr(err, self.addr))
+    }
+}
+
+/// An error from a `ReconnectingStream`, along with the address it connects to. It has the same
+/// kind and message as the original error.
+#[derive(Debug)]
+struct ConnectionError {
+    addr: SocketAddr,
+    err: io::Error,
+}
+
+impl fmt::Display for ConnectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { self.err.fmt(f) }
+}
+
+impl error::Error for ConnectionError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> { self.err.source() }
+}
+
+fn with_addr(err: io::Error, addr: SocketAddr) -> io::Error {
+    if error_addr(&err).is_some() { return err }
+    io::Error::new(err.kind(), ConnectionError { addr, err })
+}
+
+/// The address of the `ReconnectingStream` an error came from, if it came from one.
+pub fn error_addr(err: &io::Error) -> Option<SocketAddr> {
+    err.get_ref().and_then(|err| err.downcast_ref::<ConnectionError>()).map(|err| err.addr)
+}
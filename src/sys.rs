@@ -0,0 +1,64 @@
+//! Platform-specific socket operations which the standard library does not expose.
+
+use std::io;
+use std::net;
+use std::time::Duration;
+
+use std::cmp;
+
+#[cfg(unix)]
+use std::mem;
+#[cfg(unix)]
+use std::os::unix::io::AsRawFd;
+
+#[cfg(unix)]
+use libc;
+
+#[cfg(unix)]
+pub fn set_keepalive(s: &net::TcpStream, on: bool) -> io::Result<()> {
+    let val = on as libc::c_int;
+    let ret = unsafe {
+        libc::setsockopt(s.as_raw_fd(), libc::SOL_SOCKET, libc::SO_KEEPALIVE,
+                         &val as *const libc::c_int as *const libc::c_void,
+                         mem::size_of::<libc::c_int>() as libc::socklen_t)
+    };
+    if ret == 0 { Ok(()) } else { Err(io::Error::last_os_error()) }
+}
+
+#[cfg(unix)]
+pub fn is_read_ready(s: &net::TcpStream, timeout: Duration) -> io::Result<bool> {
+    // Round up, so that a short but nonzero timeout doesn't become a poll which never waits.
+    let mut millis = timeout.as_millis();
+    if Duration::from_millis(millis as u64) < timeout { millis += 1; }
+    let millis = cmp::min(millis, libc::c_int::MAX as u128) as libc::c_int;
+    let mut fd = libc::pollfd { fd: s.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+    match unsafe { libc::poll(&mut fd, 1, millis) } {
+        -1 => Err(io::Error::last_os_error()),
+        0 => Ok(false),
+        _ => Ok(true),
+    }
+}
+
+/// Without `poll`, wait for readability by peeking at the stream with a read timeout. The
+/// blocking mode of the socket can't be queried here, so it is left alone: a zero timeout is
+/// raised to the shortest read timeout instead, and a nonblocking socket doesn't wait at all.
+#[cfg(not(unix))]
+pub fn is_read_ready(s: &net::TcpStream, timeout: Duration) -> io::Result<bool> {
+    let previous = s.read_timeout()?;
+    s.set_read_timeout(Some(cmp::max(timeout, Duration::from_millis(1))))?;
+    let result = s.peek(&mut [0]);
+    s.set_read_timeout(previous)?;
+    match result {
+        Ok(..) => Ok(true),
+        Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => Ok(false),
+        Err(ref err) if err.kind() == io::ErrorKind::TimedOut => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Checks readiness without waiting, then peeks to tell data apart from EOF. A readable stream
+/// has data, EOF or an error pending, so the peek won't block.
+pub fn has_data(s: &net::TcpStream) -> io::Result<bool> {
+    if !is_read_ready(s, Duration::from_secs(0))? { return Ok(false) }
+    Ok(s.peek(&mut [0])? > 0)
+}